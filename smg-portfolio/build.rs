@@ -0,0 +1,36 @@
+//! Compiles the posts in `content/` into the binary.
+//!
+//! Every `content/<slug>.md` becomes an `include_str!` entry in
+//! `$OUT_DIR/posts.rs`, which `blog.rs` includes, so the site never fetches
+//! posts at runtime and adding a post needs no code change.
+
+use std::fmt::Write;
+use std::path::Path;
+use std::{env, fs};
+
+fn main() {
+    let content = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("content");
+    println!("cargo::rerun-if-changed={}", content.display());
+
+    let mut posts = Vec::new();
+    if let Ok(entries) = fs::read_dir(&content) {
+        for entry in entries {
+            let path = entry.expect("content/ is readable").path();
+            if path.extension().is_none_or(|ext| ext != "md") {
+                continue;
+            }
+            let slug = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string();
+            let slug_ok = !slug.is_empty() && slug.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+            assert!(slug_ok, "{}: post file names must be lowercase letters, digits and dashes", path.display());
+            posts.push((slug, path));
+        }
+    }
+    posts.sort();
+
+    let mut out = String::from("pub const SOURCES: &[(&str, &str)] = &[\n");
+    for (slug, path) in &posts {
+        writeln!(out, "    ({slug:?}, include_str!({:?})),", path.display().to_string()).unwrap();
+    }
+    out.push_str("];\n");
+    fs::write(Path::new(&env::var("OUT_DIR").unwrap()).join("posts.rs"), out).expect("OUT_DIR is writable");
+}
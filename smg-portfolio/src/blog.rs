@@ -0,0 +1,162 @@
+//! The posts behind `/writing`.
+//!
+//! Posts are `content/<slug>.md`: TOML front matter between `+++` lines,
+//! then Markdown. `build.rs` embeds every file, so a new post needs no code
+//! change, and they are parsed on first use like the resume. Drafts only
+//! show in debug builds.
+//!
+//! ```text
+//! +++
+//! title = "Why LinkedStack never blocks"
+//! date = 2025-10-01
+//! summary = "One line for the index."
+//! tags = ["rust", "lock-free"]
+//! +++
+//! ```
+
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+use toml::value::Datetime;
+
+use crate::markdown::{self, Block};
+
+mod sources {
+    include!(concat!(env!("OUT_DIR"), "/posts.rs"));
+}
+
+const FENCE: &str = "+++";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FrontMatter {
+    title: String,
+    date: Datetime,
+    summary: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    draft: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Post {
+    pub slug: String,
+    pub title: String,
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub summary: Option<String>,
+    pub tags: Vec<String>,
+    pub draft: bool,
+    pub body: Vec<Block>,
+}
+
+/// Every published post, newest first.
+pub static POSTS: LazyLock<Result<Vec<Post>, String>> = LazyLock::new(|| {
+    let mut posts = Vec::new();
+    for (slug, source) in sources::SOURCES {
+        let post = parse(slug, source).map_err(|err| format!("content/{slug}.md: {err}"))?;
+        if !post.draft || cfg!(debug_assertions) {
+            posts.push(post);
+        }
+    }
+    posts.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.slug.cmp(&b.slug)));
+    tracing::info!(posts = posts.len(), "posts parsed");
+    Ok(posts)
+});
+
+pub fn post(slug: &str) -> Option<&'static Post> {
+    POSTS.as_ref().ok()?.iter().find(|post| post.slug == slug)
+}
+
+/// Every tag once, in the order the newest posts use them.
+pub fn tags(posts: &[Post]) -> Vec<&str> {
+    let mut tags: Vec<&str> = Vec::new();
+    for tag in posts.iter().flat_map(|post| &post.tags) {
+        if !tags.contains(&tag.as_str()) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+fn parse(slug: &str, source: &str) -> Result<Post, String> {
+    let source = source.replace("\r\n", "\n");
+    let (front, body) = source
+        .strip_prefix(FENCE)
+        .and_then(|rest| rest.split_once(&format!("\n{FENCE}")))
+        .ok_or_else(|| format!("front matter must open and close with a `{FENCE}` line"))?;
+    let front: FrontMatter = toml::from_str(front).map_err(|err| format!("front matter: {err}"))?;
+    let date = match (front.date.date, front.date.time) {
+        (Some(date), None) => date.to_string(),
+        _ => return Err(format!("date `{}` must be a plain date such as 2025-10-01", front.date)),
+    };
+    if front.title.trim().is_empty() {
+        return Err("title is empty".to_string());
+    }
+    if let Some(tag) = front.tags.iter().find(|tag| tag.trim().is_empty() || tag.contains('/')) {
+        return Err(format!("tag `{tag}` must be non-empty and have no `/`"));
+    }
+
+    Ok(Post {
+        slug: slug.to_string(),
+        title: front.title,
+        date,
+        summary: front.summary,
+        tags: front.tags,
+        draft: front.draft,
+        body: markdown::parse(body.trim_start_matches(|c| c != '\n'))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::BASE_PATH;
+
+    const FRONT: &str = "+++\ntitle = \"T\"\ndate = 2025-10-01\n";
+
+    #[test]
+    fn every_content_file_parses() {
+        assert!(!sources::SOURCES.is_empty(), "build.rs found no content/*.md");
+        assert!(sources::SOURCES.windows(2).all(|pair| pair[0].0 < pair[1].0), "build.rs sorts by slug");
+        for (slug, source) in sources::SOURCES {
+            let post = parse(slug, source).unwrap_or_else(|err| panic!("content/{slug}.md: {err}"));
+            assert!(!post.body.is_empty(), "content/{slug}.md has no body");
+            // `markdown::href` adds the base path; a post that spells it out gets it twice.
+            assert!(!source.contains(&format!("]({BASE_PATH}")), "content/{slug}.md links to {BASE_PATH}");
+        }
+        assert!(POSTS.is_ok());
+    }
+
+    #[test]
+    fn reads_front_matter() {
+        let post = parse("a-post", &format!("{FRONT}tags = [\"rust\"]\n+++\r\n\r\nHello.\r\n")).unwrap();
+        assert_eq!((post.title.as_str(), post.date.as_str()), ("T", "2025-10-01"));
+        assert_eq!(post.tags, ["rust"]);
+        assert!(!post.draft);
+        assert_eq!(post.body, markdown::parse("Hello.").unwrap());
+    }
+
+    #[test]
+    fn rejects_bad_front_matter() {
+        let cases = [
+            ("no front matter\n".to_string(), "front matter must open and close"),
+            (format!("{FRONT}body without a closing fence\n"), "front matter must open and close"),
+            ("+++\ntitle = \"T\"\ndate = 2025-10-01T10:00:00\n+++\n".to_string(), "must be a plain date"),
+            (format!("{FRONT}tags = [\"a/b\"]\n+++\n"), "tag `a/b`"),
+            (format!("{FRONT}author = \"x\"\n+++\n"), "front matter: "),
+            (format!("{FRONT}+++\n<p>raw</p>\n"), "raw HTML"),
+        ];
+        for (source, expected) in cases {
+            let err = parse("a-post", &source).unwrap_err();
+            assert!(err.contains(expected), "{expected}: {err}");
+        }
+    }
+
+    #[test]
+    fn tags_follow_the_newest_posts() {
+        let post = |tags: &[&str]| parse("p", &format!("{FRONT}tags = {tags:?}\n+++\n")).unwrap();
+        assert_eq!(tags(&[post(&["b", "a"]), post(&["a", "c"])]), ["b", "a", "c"]);
+    }
+}
@@ -0,0 +1,324 @@
+//! Races `inspect_element` against `pop` at the end of the array.
+//!
+//! Readers compute an index from `len()`, yield, and only then call
+//! `inspect_element`, just like the PIPELINE transformer does with `len / 2`.
+//! Meanwhile a shrinker pops and an optional refiller pushes. Every element
+//! carries a unique tag, so each access can be classified:
+//!
+//! - `Hit`: the closure ran on the element that was at `idx` when `len` was
+//!   read.
+//! - `Skipped`: `idx` was no longer below `len`, so the closure never ran.
+//!   `inspect_element` checks the length under its reader count and returns
+//!   silently; it never panics and never reports the miss.
+//! - `Different`: the closure ran, but on an element pushed after the
+//!   original was popped. The index is valid, the element is not the one
+//!   the caller meant.
+//!
+//! The explorer uses its own array so it does not wreck the matrix demo.
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::LazyLock;
+
+use dioxus::prelude::*;
+use rsmg_core::prim::array::ContiguousArray;
+use tracing::{debug, info_span, warn, Instrument};
+
+use crate::pop_errors::{drain, pop_with_retry, PopErrorLog, PopOp, NO_RETRY};
+use crate::ui::{Button, LogView, Panel, SpecCard, Variant};
+
+static BOUNDARY_ARRAY: LazyLock<ContiguousArray<i32>> = LazyLock::new(ContiguousArray::new);
+static NEXT_TAG: AtomicI32 = AtomicI32::new(1);
+
+const SEED_LEN: usize = 24;
+const READERS: usize = 2;
+const ROUNDS: usize = 40;
+const RECENT_CAPACITY: usize = 14;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndexStrategy {
+    /// `len - 1`: the slot `pop` removes next.
+    Last,
+    /// `len / 2`, as the PIPELINE transformer computes it.
+    Middle,
+    /// `len`: already out of bounds before anyone pops.
+    PastEnd,
+}
+
+impl IndexStrategy {
+    pub const ALL: [IndexStrategy; 3] = [IndexStrategy::Last, IndexStrategy::Middle, IndexStrategy::PastEnd];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            IndexStrategy::Last => "len - 1",
+            IndexStrategy::Middle => "len / 2",
+            IndexStrategy::PastEnd => "len (out of bounds)",
+        }
+    }
+
+    fn index(self, len: usize) -> usize {
+        match self {
+            IndexStrategy::Last => len.saturating_sub(1),
+            IndexStrategy::Middle => len / 2,
+            IndexStrategy::PastEnd => len,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessOutcome {
+    Hit,
+    Skipped,
+    Different,
+}
+
+impl AccessOutcome {
+    pub fn label(self) -> &'static str {
+        match self {
+            AccessOutcome::Hit => "ran on intended element",
+            AccessOutcome::Skipped => "closure skipped",
+            AccessOutcome::Different => "ran on a different element",
+        }
+    }
+
+    pub fn class(self) -> &'static str {
+        match self {
+            AccessOutcome::Hit => "outcome-hit",
+            AccessOutcome::Skipped => "outcome-skipped",
+            AccessOutcome::Different => "outcome-different",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundaryProbe {
+    pub reader: usize,
+    pub round: usize,
+    pub idx: usize,
+    pub stale_len: usize,
+    /// Tag at `idx` when `stale_len` was read; `None` past the end.
+    pub expected: Option<i32>,
+    /// Tag the closure saw; `None` when it did not run.
+    pub seen: Option<i32>,
+    pub outcome: AccessOutcome,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BoundaryStats {
+    pub hits: usize,
+    pub skipped: usize,
+    pub different: usize,
+    pub pops: usize,
+    pub pushes: usize,
+    pub running: usize,
+    recent: VecDeque<BoundaryProbe>,
+}
+
+impl BoundaryStats {
+    /// Newest first.
+    pub fn recent(&self) -> impl Iterator<Item = &BoundaryProbe> {
+        self.recent.iter().rev()
+    }
+
+    fn record(&mut self, probe: BoundaryProbe) {
+        match probe.outcome {
+            AccessOutcome::Hit => self.hits += 1,
+            AccessOutcome::Skipped => self.skipped += 1,
+            AccessOutcome::Different => self.different += 1,
+        }
+        if self.recent.len() == RECENT_CAPACITY {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(probe);
+    }
+}
+
+pub fn len() -> usize {
+    BOUNDARY_ARRAY.len()
+}
+
+fn read(idx: usize) -> Option<i32> {
+    let seen = Cell::new(None);
+    BOUNDARY_ARRAY.inspect_element(idx, |v| seen.set(Some(*v)));
+    seen.get()
+}
+
+fn push_tagged() {
+    BOUNDARY_ARRAY.push(NEXT_TAG.fetch_add(1, Ordering::Relaxed));
+}
+
+/// Refills the array with fresh tags and launches readers, a shrinker and
+/// optionally a refiller, once the old tags are [`drain`]ed.
+pub fn run(
+    strategy: IndexStrategy,
+    refill: bool,
+    mut stats: Signal<BoundaryStats>,
+    pop_errors: Signal<PopErrorLog>,
+) {
+    stats.set(BoundaryStats {
+        running: READERS + 1 + usize::from(refill),
+        ..BoundaryStats::default()
+    });
+    spawn(
+        async move {
+            if let Err(err) = drain(PopOp::BoundaryReseed, pop_errors, || BOUNDARY_ARRAY.pop()).await {
+                warn!(error = %err, "boundary race not started: reseed drain failed");
+                stats.write().running = 0;
+                return;
+            }
+            for _ in 0..SEED_LEN {
+                push_tagged();
+            }
+            race(strategy, refill, stats, pop_errors);
+        }
+        .instrument(info_span!("boundary_reseed")),
+    );
+}
+
+fn race(strategy: IndexStrategy, refill: bool, mut stats: Signal<BoundaryStats>, pop_errors: Signal<PopErrorLog>) {
+    for reader in 0..READERS {
+        spawn(
+            async move {
+                for round in 0..ROUNDS {
+                    let stale_len = BOUNDARY_ARRAY.len();
+                    let idx = strategy.index(stale_len);
+                    let expected = read(idx);
+                    // Yield so the shrinker can run between the length read and the access.
+                    gloo_timers::future::TimeoutFuture::new(3 + (round as u32 * 7 + reader as u32 * 5) % 9).await;
+                    let seen = read(idx);
+                    let outcome = match (expected, seen) {
+                        (_, None) => AccessOutcome::Skipped,
+                        (Some(expected), Some(seen)) if expected == seen => AccessOutcome::Hit,
+                        _ => AccessOutcome::Different,
+                    };
+                    debug!(idx, stale_len, ?expected, ?seen, ?outcome, "boundary access");
+                    stats.write().record(BoundaryProbe {
+                        reader,
+                        round,
+                        idx,
+                        stale_len,
+                        expected,
+                        seen,
+                        outcome,
+                    });
+                }
+                stats.write().running -= 1;
+            }
+            .instrument(info_span!("boundary_reader", reader)),
+        );
+    }
+
+    spawn(
+        async move {
+            for _ in 0..ROUNDS * 2 {
+                let popped = pop_with_retry(PopOp::BoundaryShrink, NO_RETRY, pop_errors, || BOUNDARY_ARRAY.pop()).await;
+                if let Ok(Some(tag)) = popped {
+                    debug!(tag, len = BOUNDARY_ARRAY.len(), "boundary pop");
+                    stats.write().pops += 1;
+                }
+                gloo_timers::future::TimeoutFuture::new(4).await;
+            }
+            stats.write().running -= 1;
+        }
+        .instrument(info_span!("boundary_shrinker")),
+    );
+
+    if refill {
+        spawn(
+            async move {
+                for _ in 0..ROUNDS * 2 {
+                    push_tagged();
+                    stats.write().pushes += 1;
+                    gloo_timers::future::TimeoutFuture::new(5).await;
+                }
+                stats.write().running -= 1;
+            }
+            .instrument(info_span!("boundary_refiller")),
+        );
+    }
+}
+
+/// Races stale-index `inspect_element` calls against `pop` and classifies
+/// what each closure actually touched.
+#[component]
+pub fn BoundaryExplorer(pop_errors: Signal<PopErrorLog>) -> Element {
+    let stats = use_signal(BoundaryStats::default);
+    let mut strategy = use_signal(|| IndexStrategy::Middle);
+    let mut refill = use_signal(|| true);
+    let current = stats.read();
+
+    rsx! {
+        Panel { id: "boundary-area",
+            SpecCard { label: "EXPLORER:", value: "inspect_element(stale_idx) vs pop()",
+                "Readers compute an index from "
+                strong { "len()" }
+                ", yield, then call "
+                strong { "inspect_element" }
+                " while a shrinker pops and an optional refiller pushes. "
+                "Every element carries a unique tag, so each access is classified as a hit on the intended element, "
+                "a skipped closure, or a hit on a different element that now occupies the slot."
+            }
+
+            div { class: "trace-toolbar",
+                label {
+                    "Index "
+                    select {
+                        onchange: move |evt| {
+                            let picked = IndexStrategy::ALL
+                                .into_iter()
+                                .find(|candidate| candidate.label() == evt.value())
+                                .unwrap_or(IndexStrategy::Middle);
+                            strategy.set(picked);
+                        },
+                        for candidate in IndexStrategy::ALL {
+                            option {
+                                value: "{candidate.label()}",
+                                selected: *strategy.read() == candidate,
+                                "{candidate.label()}"
+                            }
+                        }
+                    }
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: *refill.read(),
+                        onchange: move |evt| refill.set(evt.checked()),
+                    }
+                    " refill with push"
+                }
+                Button {
+                    variant: Variant::Race,
+                    loading: current.running > 0,
+                    onclick: move |_| {
+                        info!(strategy = strategy().label(), refill = refill(), "boundary race started");
+                        run(strategy(), refill(), stats, pop_errors);
+                    },
+                    if current.running > 0 { "RACING..." } else { "RACE INDEX VS POP" }
+                }
+            }
+
+            ul { class: "chaos-stats",
+                li { class: "outcome-hit", "ran on intended element: {current.hits}" }
+                li { class: "outcome-skipped", "closure skipped (index past len): {current.skipped}" }
+                li { class: "outcome-different", "ran on a different element: {current.different}" }
+                li { "pops: {current.pops}, pushes: {current.pushes}, len now: {len()}" }
+            }
+
+            LogView {
+                title: "Recent Accesses",
+                empty: current.recent().next().is_none(),
+                placeholder: "Run the race to classify boundary accesses.",
+                for probe in current.recent() {
+                    div {
+                        class: "log-entry {probe.outcome.class()}",
+                        key: "{probe.reader}-{probe.round}",
+                        "reader {probe.reader} #{probe.round}: idx {probe.idx} from len {probe.stale_len}, "
+                        "expected {probe.expected:?}, saw {probe.seen:?} → {probe.outcome.label()}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! The few browser APIs the pages use directly: the location, for share
+//! links, file downloads, printing, attributes on `<html>` for the theme and
+//! the deep link `404.html` hands over. Native builds get stubs.
+
+pub use imp::{download, fragment, origin, print, set_fragment, set_root_attribute, take_deep_link};
+
+/// The `sessionStorage` key `discovery::not_found` leaves the requested path
+/// under before sending the browser to the app.
+pub const DEEP_LINK_KEY: &str = "smg-portfolio.deep-link";
+
+#[cfg(target_arch = "wasm32")]
+mod imp {
+    use wasm_bindgen::{JsCast, JsValue};
+    use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+    pub fn fragment() -> Option<String> {
+        web_sys::window()?.location().hash().ok().filter(|hash| !hash.is_empty())
+    }
+
+    pub fn set_fragment(fragment: &str) {
+        if let Some(window) = web_sys::window() {
+            let _ = window.location().set_hash(fragment);
+        }
+    }
+
+    pub fn origin() -> String {
+        web_sys::window()
+            .and_then(|window| window.location().origin().ok())
+            .unwrap_or_default()
+    }
+
+    /// Saves `contents` as `name` through a temporary object URL and anchor.
+    pub fn download(name: &str, mime: &str, contents: &str) -> Result<(), String> {
+        let save = || {
+            let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+            let options = BlobPropertyBag::new();
+            options.set_type(mime);
+            let blob = Blob::new_with_str_sequence_and_options(&parts, &options)?;
+            let url = Url::create_object_url_with_blob(&blob)?;
+            let document = web_sys::window().and_then(|w| w.document()).ok_or("no document")?;
+            let anchor = document.create_element("a")?.dyn_into::<HtmlAnchorElement>()?;
+            anchor.set_href(&url);
+            anchor.set_download(name);
+            anchor.click();
+            Url::revoke_object_url(&url)
+        };
+        save().map_err(|err: JsValue| format!("{err:?}"))
+    }
+
+    pub fn print() {
+        if let Some(window) = web_sys::window() {
+            let _ = window.print();
+        }
+    }
+
+    /// Sets `name` on `<html>`, or removes it for `None`.
+    pub fn set_root_attribute(name: &str, value: Option<&str>) {
+        let Some(root) = web_sys::window().and_then(|w| w.document()).and_then(|d| d.document_element()) else {
+            return;
+        };
+        let _ = match value {
+            Some(value) => root.set_attribute(name, value),
+            None => root.remove_attribute(name),
+        };
+    }
+
+    /// The path, relative to the base path, that `404.html` was asked for,
+    /// removed so a reload does not route there again.
+    pub fn take_deep_link() -> Option<String> {
+        let storage = web_sys::window()?.session_storage().ok()??;
+        let path = storage.get_item(super::DEEP_LINK_KEY).ok()??;
+        let _ = storage.remove_item(super::DEEP_LINK_KEY);
+        Some(path)
+    }
+}
+
+/// Native builds have no address bar or downloads folder to talk to.
+#[cfg(not(target_arch = "wasm32"))]
+mod imp {
+    pub fn fragment() -> Option<String> {
+        None
+    }
+
+    pub fn set_fragment(_fragment: &str) {}
+
+    pub fn origin() -> String {
+        String::new()
+    }
+
+    pub fn download(_name: &str, _mime: &str, _contents: &str) -> Result<(), String> {
+        Err("downloads need the web build".to_string())
+    }
+
+    pub fn print() {}
+
+    pub fn set_root_attribute(_name: &str, _value: Option<&str>) {}
+
+    pub fn take_deep_link() -> Option<String> {
+        None
+    }
+}
@@ -0,0 +1,376 @@
+//! Fault injection for `inspect_element` callbacks.
+//!
+//! With chaos enabled, a fraction of the mutation closures passed through
+//! `mutate_element` misbehave:
+//!
+//! - `EarlyReturn` writes half of the intended change and returns. The
+//!   primitive releases the element normally; only the value is torn.
+//! - `Panic` writes half of the change and panics. `inspect_element` has no
+//!   unwind guard, so the element stamp stays in its writing state and the
+//!   array-wide `locker_count` is never decremented. Afterwards any access to
+//!   that index, and every `push`/`pop`, spins forever. Only available on
+//!   native builds: wasm compiles with `panic = "abort"`, where the same fault
+//!   tears down the whole module instead.
+//!
+//! Once an index is poisoned, the demo helpers stop touching it and refuse
+//! `push`/`pop`, otherwise the single UI thread would hang in the spin loop.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ui::{Button, Variant};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultMode {
+    Off,
+    EarlyReturn,
+    Panic,
+}
+
+impl FaultMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            FaultMode::Off => "off",
+            FaultMode::EarlyReturn => "early return",
+            FaultMode::Panic => "panic",
+        }
+    }
+
+    /// Whether this build can catch the panic at all.
+    pub fn supported(self) -> bool {
+        self != FaultMode::Panic || cfg!(not(target_arch = "wasm32"))
+    }
+}
+
+/// What the last fault at an index left behind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellFault {
+    None,
+    Torn { before: i32, intended: i32, left: i32 },
+    Poisoned { before: i32, intended: i32, left: i32 },
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FaultReport {
+    pub early_returns: usize,
+    pub panics: usize,
+    /// Indices left locked by a panic.
+    pub poisoned: Vec<usize>,
+    /// `len()` at the moment of the first panic.
+    pub len_at_first_panic: Option<usize>,
+    /// Successful mutations on other indices after the first fault.
+    pub bystander_mutations: usize,
+    /// `push`/`pop` calls refused because they would spin forever.
+    pub refused_major_ops: usize,
+}
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+/// A fault fires on roughly one in `RATE` mutations.
+static RATE: AtomicU64 = AtomicU64::new(10);
+const DEFAULT_SEED: u64 = 0x2545_F491_4F6C_DD1D;
+static RNG_STATE: AtomicU64 = AtomicU64::new(DEFAULT_SEED);
+
+static EARLY_RETURNS: AtomicUsize = AtomicUsize::new(0);
+static PANICS: AtomicUsize = AtomicUsize::new(0);
+static BYSTANDERS: AtomicUsize = AtomicUsize::new(0);
+static REFUSED: AtomicUsize = AtomicUsize::new(0);
+static FIRST_PANIC_LEN: AtomicUsize = AtomicUsize::new(usize::MAX);
+static CELL_FAULTS: LazyLock<Mutex<BTreeMap<usize, CellFault>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+pub fn mode() -> FaultMode {
+    match MODE.load(Ordering::Relaxed) {
+        1 => FaultMode::EarlyReturn,
+        2 => FaultMode::Panic,
+        _ => FaultMode::Off,
+    }
+}
+
+pub fn set_mode(mode: FaultMode) {
+    if !mode.supported() {
+        return;
+    }
+    let raw = match mode {
+        FaultMode::Off => 0,
+        FaultMode::EarlyReturn => 1,
+        FaultMode::Panic => 2,
+    };
+    MODE.store(raw, Ordering::Relaxed);
+    tracing::info!(mode = mode.label(), "chaos mode changed");
+}
+
+pub fn rate() -> u64 {
+    RATE.load(Ordering::Relaxed)
+}
+
+pub fn set_rate(one_in: u64) {
+    RATE.store(one_in.max(1), Ordering::Relaxed);
+}
+
+/// The generator state; rolls continue from here.
+pub fn seed() -> u64 {
+    RNG_STATE.load(Ordering::Relaxed)
+}
+
+/// Restarts the generator. xorshift never leaves zero, so zero means the
+/// page-load seed.
+pub fn set_seed(seed: u64) {
+    RNG_STATE.store(if seed == 0 { DEFAULT_SEED } else { seed }, Ordering::Relaxed);
+}
+
+/// Rolls the dice for one mutation. xorshift64 is plenty for a demo and keeps
+/// runs reproducible from a fresh page load.
+pub fn roll() -> Option<FaultMode> {
+    let mode = mode();
+    if mode == FaultMode::Off {
+        return None;
+    }
+    let mut x = RNG_STATE.load(Ordering::Relaxed);
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    RNG_STATE.store(x, Ordering::Relaxed);
+    x.is_multiple_of(rate()).then_some(mode)
+}
+
+/// Half of the way from `before` to `intended`: what a callback that bailed
+/// out mid-update leaves in the element.
+pub fn partial(before: i32, intended: i32) -> i32 {
+    // Widened: a wrapped `intended` can be a full `i32` range away.
+    (i64::from(before) + (i64::from(intended) - i64::from(before)) / 2) as i32
+}
+
+pub fn cell_fault(idx: usize) -> CellFault {
+    CELL_FAULTS
+        .lock()
+        .unwrap()
+        .get(&idx)
+        .copied()
+        .unwrap_or(CellFault::None)
+}
+
+pub fn is_poisoned(idx: usize) -> bool {
+    matches!(cell_fault(idx), CellFault::Poisoned { .. })
+}
+
+/// True once any panic has leaked a `locker_count` slot.
+pub fn array_poisoned() -> bool {
+    PANICS.load(Ordering::Acquire) > 0
+}
+
+pub fn record_early_return(idx: usize, before: i32, intended: i32, left: i32) {
+    EARLY_RETURNS.fetch_add(1, Ordering::Relaxed);
+    CELL_FAULTS
+        .lock()
+        .unwrap()
+        .insert(idx, CellFault::Torn { before, intended, left });
+    tracing::warn!(idx, before, intended, left, "injected early return");
+}
+
+pub fn record_panic(idx: usize, before: i32, intended: i32, left: i32, len: usize) {
+    if PANICS.fetch_add(1, Ordering::AcqRel) == 0 {
+        FIRST_PANIC_LEN.store(len, Ordering::Relaxed);
+    }
+    CELL_FAULTS
+        .lock()
+        .unwrap()
+        .insert(idx, CellFault::Poisoned { before, intended, left });
+    tracing::error!(idx, before, intended, left, len, "injected panic inside inspect_element");
+}
+
+/// Counts a clean mutation if an earlier fault could have affected it.
+pub fn record_mutation(idx: usize) {
+    if EARLY_RETURNS.load(Ordering::Relaxed) + PANICS.load(Ordering::Relaxed) == 0 {
+        return;
+    }
+    if !matches!(cell_fault(idx), CellFault::None) {
+        return;
+    }
+    BYSTANDERS.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn record_refused(op: &str) {
+    REFUSED.fetch_add(1, Ordering::Relaxed);
+    tracing::warn!(op, "refused: a poisoned inspect_element leaked locker_count");
+}
+
+/// Forgets torn cells after the matrix is reseeded. Poisoned indices stay:
+/// the singleton cannot be rebuilt without a reload.
+pub fn clear_torn() {
+    CELL_FAULTS
+        .lock()
+        .unwrap()
+        .retain(|_, fault| matches!(fault, CellFault::Poisoned { .. }));
+}
+
+pub fn report() -> FaultReport {
+    let faults = CELL_FAULTS.lock().unwrap();
+    let first_len = FIRST_PANIC_LEN.load(Ordering::Relaxed);
+    FaultReport {
+        early_returns: EARLY_RETURNS.load(Ordering::Relaxed),
+        panics: PANICS.load(Ordering::Relaxed),
+        poisoned: faults
+            .iter()
+            .filter(|(_, fault)| matches!(fault, CellFault::Poisoned { .. }))
+            .map(|(idx, _)| *idx)
+            .collect(),
+        len_at_first_panic: (first_len != usize::MAX).then_some(first_len),
+        bystander_mutations: BYSTANDERS.load(Ordering::Relaxed),
+        refused_major_ops: REFUSED.load(Ordering::Relaxed),
+    }
+}
+
+/// Result of probing a poisoned array from a helper thread.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProbeReport {
+    pub len: usize,
+    pub neighbour: Option<String>,
+    pub poisoned_index: String,
+    pub push: String,
+}
+
+/// Probes the poisoned array from helper threads so the UI thread never
+/// enters a spin loop. A probe that does not finish within the timeout is
+/// left spinning on its own thread; the array is unusable either way.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn probe(array: &'static rsmg_core::prim::array::ContiguousArray<i32>) -> Option<ProbeReport> {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_millis(200);
+
+    let report = report();
+    let poisoned = *report.poisoned.first()?;
+    let len = array.len();
+
+    let run = |label: &str, op: Box<dyn FnOnce() -> String + Send>| -> String {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(op());
+        });
+        match rx.recv_timeout(TIMEOUT) {
+            Ok(outcome) => outcome,
+            Err(_) => {
+                tracing::warn!(probe = label, "probe still spinning after timeout");
+                format!("still spinning after {}ms", TIMEOUT.as_millis())
+            }
+        }
+    };
+
+    let neighbour = (0..len).find(|idx| !is_poisoned(*idx)).map(|idx| {
+        run(
+            "neighbour",
+            Box::new(move || {
+                let value = std::cell::Cell::new(None);
+                array.inspect_element(idx, |v| value.set(Some(*v)));
+                format!("index {idx} read {:?}", value.get())
+            }),
+        )
+    });
+    let poisoned_index = run(
+        "poisoned",
+        Box::new(move || {
+            array.inspect_element(poisoned, |_| {});
+            format!("index {poisoned} returned")
+        }),
+    );
+    let push = run(
+        "push",
+        Box::new(move || {
+            array.push(0);
+            "push returned".to_string()
+        }),
+    );
+
+    Some(ProbeReport {
+        len,
+        neighbour,
+        poisoned_index,
+        push,
+    })
+}
+
+/// Chaos toggle for `inspect_element` callbacks and what it has done so far.
+/// `mode` and `rate` come from `App` so a restored scenario shows up here.
+#[component]
+pub fn ChaosPanel(report: FaultReport, len: usize, mode: FaultMode, rate: u64) -> Element {
+    #[allow(unused_mut)]
+    let mut probed = use_signal(|| None::<ProbeReport>);
+
+    rsx! {
+        div { class: "chaos-panel",
+            div { class: "log-header", "Fault Injection" }
+            div { class: "trace-toolbar",
+                label {
+                    "Mode "
+                    select {
+                        onchange: move |evt| {
+                            let new_mode = match evt.value().as_str() {
+                                "early" => FaultMode::EarlyReturn,
+                                "panic" => FaultMode::Panic,
+                                _ => FaultMode::Off,
+                            };
+                            set_mode(new_mode);
+                        },
+                        option { value: "off", selected: mode == FaultMode::Off, "OFF" }
+                        option { value: "early", selected: mode == FaultMode::EarlyReturn, "EARLY RETURN" }
+                        option {
+                            value: "panic",
+                            disabled: !FaultMode::Panic.supported(),
+                            selected: mode == FaultMode::Panic,
+                            "PANIC (native only)"
+                        }
+                    }
+                }
+                label {
+                    "Rate "
+                    select {
+                        onchange: move |evt| {
+                            set_rate(evt.value().parse().unwrap_or(10));
+                        },
+                        for one_in in [5u64, 10, 50] {
+                            option { value: "{one_in}", selected: rate == one_in, "1 in {one_in}" }
+                        }
+                    }
+                }
+                if cfg!(not(target_arch = "wasm32")) && !report.poisoned.is_empty() {
+                    Button {
+                        variant: Variant::Secondary,
+                        onclick: move |_| {
+                            #[cfg(not(target_arch = "wasm32"))]
+                            probed.set(probe(&crate::CONTIGUOUS_ARRAY));
+                        },
+                        "PROBE"
+                    }
+                }
+            }
+            ul { class: "chaos-stats",
+                li { "early returns: {report.early_returns} (element torn, primitive unaffected)" }
+                li { "panics: {report.panics}, poisoned indices: {report.poisoned:?}" }
+                li { "clean mutations on other indices since the first fault: {report.bystander_mutations}" }
+                li {
+                    "len(): {len}"
+                    if let Some(first) = report.len_at_first_panic {
+                        " (was {first} at the first panic)"
+                    }
+                }
+                li { "push/pop refused to avoid a spin-forever: {report.refused_major_ops}" }
+            }
+            if let Some(probe) = probed.read().as_ref() {
+                ul { class: "chaos-stats chaos-probe",
+                    li { "probe len(): {probe.len}" }
+                    if let Some(neighbour) = &probe.neighbour {
+                        li { "probe neighbour: {neighbour}" }
+                    }
+                    li { "probe poisoned index: {probe.poisoned_index}" }
+                    li { "probe push: {probe.push}" }
+                }
+            }
+        }
+    }
+}
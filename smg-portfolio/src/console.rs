@@ -0,0 +1,545 @@
+//! Commands typed into the console panel.
+//!
+//! A command works on the array unless it starts with `stack` (or `array`),
+//! or `use stack` changed the default. Every command goes through the same
+//! helpers as the buttons, so chaos, snapshot tracking and pop error
+//! accounting see console traffic too.
+
+use std::cell::Cell;
+
+use dioxus::prelude::*;
+use tracing::{debug, info_span, Instrument};
+
+use crate::pop_errors::{PopOp, DRAIN_RETRY, NO_RETRY};
+use crate::ui::{Panel, SpecCard};
+use crate::{chaos, mutate_element, push_log, read_element, spawn_span, DemoEnv, Target, CONTIGUOUS_ARRAY};
+
+/// Lines the console keeps on screen.
+const CAPACITY: usize = 200;
+const MAX_BURST_WORKERS: usize = 16;
+const MAX_BURST_PUSHES: usize = 1_000;
+/// `show` lists at most this many elements.
+const SHOW_LIMIT: usize = 64;
+
+pub const HELP: [&str; 11] = [
+    "push N [N ...]      push values",
+    "pop                 pop one value",
+    "inspect I           read index I (array)",
+    "inspect I OP N      OP is = += -= *= /= %= (array)",
+    "len                 current length",
+    "show                list the array, or the stack depth",
+    "burst WxN           W workers push N values each, concurrently",
+    "drain               pop until empty",
+    "use array|stack     change the default target; prefix one command with `stack` instead",
+    "history             list earlier commands",
+    "clear               clear this console",
+];
+
+/// Words `complete` knows, in the order candidates are listed.
+const COMMANDS: [&str; 13] = [
+    "array", "burst", "clear", "drain", "help", "history", "inspect", "len", "pop", "push", "show", "stack", "use",
+];
+
+/// One line of console scrollback.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConsoleLine {
+    Input(String),
+    Output(String),
+    Error(String),
+}
+
+impl ConsoleLine {
+    pub fn class(&self) -> &'static str {
+        match self {
+            ConsoleLine::Input(_) => "console-line console-input",
+            ConsoleLine::Output(_) => "console-line",
+            ConsoleLine::Error(_) => "console-line console-error",
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        match self {
+            ConsoleLine::Input(text) | ConsoleLine::Output(text) | ConsoleLine::Error(text) => text,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssignOp {
+    Set,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl AssignOp {
+    fn parse(text: &str) -> Option<AssignOp> {
+        Some(match text {
+            "=" => AssignOp::Set,
+            "+=" => AssignOp::Add,
+            "-=" => AssignOp::Sub,
+            "*=" => AssignOp::Mul,
+            "/=" => AssignOp::Div,
+            "%=" => AssignOp::Rem,
+            _ => return None,
+        })
+    }
+
+    fn apply(self, value: i32, operand: i32) -> i32 {
+        match self {
+            AssignOp::Set => operand,
+            AssignOp::Add => value.wrapping_add(operand),
+            AssignOp::Sub => value.wrapping_sub(operand),
+            AssignOp::Mul => value.wrapping_mul(operand),
+            AssignOp::Div => value.wrapping_div(operand),
+            AssignOp::Rem => value.wrapping_rem(operand),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Help,
+    Use(Target),
+    Push(Target, Vec<i32>),
+    Pop(Target),
+    Inspect(usize, Option<(AssignOp, i32)>),
+    Len(Target),
+    Show(Target),
+    Burst(Target, usize, usize),
+    Drain(Target),
+    History,
+    Clear,
+}
+
+fn number<T: std::str::FromStr>(word: Option<&str>, what: &str) -> Result<T, String> {
+    let word = word.ok_or_else(|| format!("missing {what}"))?;
+    word.parse().map_err(|_| format!("`{word}` is not a valid {what}"))
+}
+
+/// Parses one line; `default` is the target when the line names none.
+pub fn parse(line: &str, default: Target) -> Result<Command, String> {
+    let mut words = line.split_whitespace().peekable();
+    let target = match words.peek() {
+        Some(&"array") => {
+            words.next();
+            Target::Array
+        }
+        Some(&"stack") => {
+            words.next();
+            Target::Stack
+        }
+        _ => default,
+    };
+    let Some(name) = words.next() else {
+        return Err("empty command; try `help`".to_string());
+    };
+    let array_only = |what: &str| {
+        if target == Target::Stack {
+            Err(format!("`{what}` needs index access; the stack only has push and pop"))
+        } else {
+            Ok(())
+        }
+    };
+    let command = match name {
+        "help" => Command::Help,
+        "use" => match words.next() {
+            Some("array") => Command::Use(Target::Array),
+            Some("stack") => Command::Use(Target::Stack),
+            _ => return Err("usage: use array|stack".to_string()),
+        },
+        "push" => {
+            let values = words.by_ref().map(|word| number(Some(word), "value")).collect::<Result<Vec<i32>, _>>()?;
+            if values.is_empty() {
+                return Err("usage: push N [N ...]".to_string());
+            }
+            Command::Push(target, values)
+        }
+        "pop" => Command::Pop(target),
+        "inspect" => {
+            array_only("inspect")?;
+            let idx = number(words.next(), "index")?;
+            match words.next() {
+                None => Command::Inspect(idx, None),
+                Some(op) => {
+                    let op = AssignOp::parse(op).ok_or_else(|| format!("unknown operator `{op}`; use = += -= *= /= %="))?;
+                    let operand = number(words.next(), "operand")?;
+                    if operand == 0 && matches!(op, AssignOp::Div | AssignOp::Rem) {
+                        return Err("division by zero".to_string());
+                    }
+                    Command::Inspect(idx, Some((op, operand)))
+                }
+            }
+        }
+        "len" => Command::Len(target),
+        "show" => Command::Show(target),
+        "burst" => {
+            let spec = words.next().ok_or("usage: burst WxN, e.g. burst 4x25")?;
+            let (workers, pushes) = spec.split_once('x').ok_or("usage: burst WxN, e.g. burst 4x25")?;
+            let workers: usize = number(Some(workers), "worker count")?;
+            let pushes: usize = number(Some(pushes), "push count")?;
+            if !(1..=MAX_BURST_WORKERS).contains(&workers) || !(1..=MAX_BURST_PUSHES).contains(&pushes) {
+                return Err(format!("burst allows 1-{MAX_BURST_WORKERS} workers and 1-{MAX_BURST_PUSHES} pushes each"));
+            }
+            Command::Burst(target, workers, pushes)
+        }
+        "drain" => Command::Drain(target),
+        "history" => Command::History,
+        "clear" => Command::Clear,
+        other => return Err(format!("unknown command `{other}`; try `help`")),
+    };
+    if let Some(extra) = words.next() {
+        return Err(format!("unexpected `{extra}` after `{name}`"));
+    }
+    Ok(command)
+}
+
+/// Result of pressing Tab.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Completion {
+    /// The line with the last word extended as far as it is unambiguous.
+    pub line: String,
+    /// Every word that still matches, when there is more than one.
+    pub candidates: Vec<&'static str>,
+}
+
+pub fn complete(line: &str) -> Completion {
+    let (head, word) = match line.rfind(' ') {
+        Some(space) => line.split_at(space + 1),
+        None => ("", line),
+    };
+    let previous = head.split_whitespace().last();
+    let pool: &[&'static str] = match previous {
+        None | Some("array" | "stack") => &COMMANDS,
+        Some("use") => &["array", "stack"],
+        _ => &[],
+    };
+    let candidates: Vec<&'static str> = pool.iter().copied().filter(|c| c.starts_with(word)).collect();
+    let completed = match candidates.as_slice() {
+        [] => word.to_string(),
+        [only] => format!("{only} "),
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.len(), |common, c| {
+                first.bytes().zip(c.bytes()).take(common).take_while(|(a, b)| a == b).count()
+            });
+            first[..common].to_string()
+        }
+    };
+    Completion {
+        line: format!("{head}{completed}"),
+        candidates: if candidates.len() > 1 { candidates } else { Vec::new() },
+    }
+}
+
+/// Runs `command` and returns what it printed. `history` and `clear` are
+/// handled by the panel itself.
+pub async fn execute(command: Command, env: DemoEnv) -> Result<Vec<String>, String> {
+    Ok(match command {
+        Command::Help => HELP.iter().map(|line| line.to_string()).collect(),
+        Command::Use(target) => vec![format!("default target is now the {}", target.label())],
+        Command::Push(target, values) => {
+            for value in &values {
+                env.push(target, *value)?;
+            }
+            env.publish(target);
+            vec![format!("pushed {values:?}; {} len {}", target.label(), env.len(target))]
+        }
+        Command::Pop(target) => {
+            let op = match target {
+                Target::Array => PopOp::ArrayConsolePop,
+                Target::Stack => PopOp::StackPop,
+            };
+            let popped = env.pop(target, op, NO_RETRY).await?;
+            env.publish(target);
+            match popped {
+                Some(value) => vec![format!("popped {value}; {} len {}", target.label(), env.len(target))],
+                None => vec![format!("{} is empty", target.label())],
+            }
+        }
+        Command::Inspect(idx, op) => {
+            let len = CONTIGUOUS_ARRAY.len();
+            if idx >= len {
+                return Err(format!("index {idx} is out of bounds (len {len})"));
+            }
+            let poisoned = chaos::is_poisoned(idx);
+            match op {
+                None if poisoned => vec![format!("[{idx}] = {} (poisoned; last known value)", read_element(idx))],
+                None => vec![format!("[{idx}] = {}", read_element(idx))],
+                Some((op, operand)) => {
+                    // `inspect_element` skips a stale index silently; report it instead.
+                    let (before, after) = mutate_element(idx, |v| *v = op.apply(*v, operand))
+                        .ok_or_else(|| format!("index {idx} was not mutated (gone or poisoned)"))?;
+                    env.publish(Target::Array);
+                    vec![format!("[{idx}] {before} → {after}")]
+                }
+            }
+        }
+        Command::Len(target) => vec![format!("{} len {}", target.label(), env.len(target))],
+        Command::Show(Target::Array) => {
+            let values: Vec<i32> = (0..CONTIGUOUS_ARRAY.len().min(SHOW_LIMIT)).map(read_element).collect();
+            let more = CONTIGUOUS_ARRAY.len().saturating_sub(SHOW_LIMIT);
+            if more > 0 {
+                vec![format!("{values:?} … and {more} more")]
+            } else {
+                vec![format!("{values:?}")]
+            }
+        }
+        Command::Show(Target::Stack) => vec![format!(
+            "stack depth {}; LinkedStack only exposes the top, try `stack pop`",
+            env.len(Target::Stack)
+        )],
+        Command::Burst(target, workers, pushes) => {
+            let start = env.len(target);
+            let refused = Cell::new(0);
+            let refused = &refused;
+            let tasks = (0..workers).map(|worker| {
+                async move {
+                    for i in 1..=pushes {
+                        let value = (start + worker * pushes + i) as i32;
+                        if env.push(target, value).is_err() {
+                            refused.set(refused.get() + 1);
+                        }
+                        gloo_timers::future::TimeoutFuture::new(0).await;
+                    }
+                    env.publish(target);
+                }
+                .instrument(spawn_span(info_span!("console_burst_worker", worker)))
+            });
+            futures_util::future::join_all(tasks).await;
+            let mut out = vec![format!(
+                "{workers} workers pushed {} values; {} len {}",
+                workers * pushes - refused.get(),
+                target.label(),
+                env.len(target)
+            )];
+            if refused.get() > 0 {
+                out.push(format!("{} pushes were refused", refused.get()));
+            }
+            out
+        }
+        Command::Drain(target) => {
+            let op = match target {
+                Target::Array => PopOp::ArrayReset,
+                Target::Stack => PopOp::StackDrain,
+            };
+            let mut drained = 0;
+            while env.pop(target, op, DRAIN_RETRY).await?.is_some() {
+                drained += 1;
+                if drained % 50 == 0 {
+                    gloo_timers::future::TimeoutFuture::new(1).await;
+                }
+            }
+            debug!(drained, "console drain");
+            env.publish(target);
+            vec![format!("drained {drained} from the {}", target.label())]
+        }
+        Command::History | Command::Clear => Vec::new(),
+    })
+}
+
+fn console_print(mut lines: Signal<Vec<ConsoleLine>>, line: ConsoleLine) {
+    let mut lines = lines.write();
+    lines.push(line);
+    if lines.len() > CAPACITY {
+        lines.remove(0);
+    }
+}
+
+/// Terminal-style access to `CONTIGUOUS_ARRAY` and `MAGIC_DATA`.
+#[component]
+pub fn ConsolePanel(env: DemoEnv) -> Element {
+    let lines = use_signal(|| vec![ConsoleLine::Output("type `help` for commands, Tab to complete, ↑/↓ for history".to_string())]);
+    let mut input = use_signal(String::new);
+    let mut history = use_signal(Vec::<String>::new);
+    let mut cursor = use_signal(|| None::<usize>);
+    let mut target = use_signal(|| Target::Array);
+
+    let onkeydown = move |evt: KeyboardEvent| match evt.key() {
+        Key::Enter => {
+            let line = input.read().trim().to_string();
+            input.set(String::new());
+            cursor.set(None);
+            if line.is_empty() {
+                return;
+            }
+            if history.read().last() != Some(&line) {
+                history.write().push(line.clone());
+            }
+            console_print(lines, ConsoleLine::Input(format!("{}> {line}", target().label())));
+            match parse(&line, target()) {
+                Err(err) => console_print(lines, ConsoleLine::Error(err)),
+                Ok(Command::Clear) => {
+                    let mut lines = lines;
+                    lines.write().clear();
+                }
+                Ok(Command::History) => {
+                    for (n, entry) in history.read().iter().enumerate() {
+                        console_print(lines, ConsoleLine::Output(format!("{:>3}  {entry}", n + 1)));
+                    }
+                }
+                Ok(command) => {
+                    if let Command::Use(picked) = command {
+                        target.set(picked);
+                    }
+                    spawn(
+                        async move {
+                            match execute(command, env).await {
+                                Ok(out) => {
+                                    for text in out {
+                                        push_log(env.race_log, format!("console: {text}"));
+                                        console_print(lines, ConsoleLine::Output(text));
+                                    }
+                                }
+                                Err(err) => {
+                                    push_log(env.race_log, format!("console: {line} failed: {err}"));
+                                    console_print(lines, ConsoleLine::Error(err));
+                                }
+                            }
+                        }
+                        .instrument(info_span!("console_command")),
+                    );
+                }
+            }
+        }
+        Key::Tab => {
+            evt.prevent_default();
+            let completion = complete(&input.read());
+            if !completion.candidates.is_empty() {
+                console_print(lines, ConsoleLine::Output(completion.candidates.join("  ")));
+            }
+            input.set(completion.line);
+        }
+        Key::ArrowUp => {
+            evt.prevent_default();
+            let len = history.read().len();
+            if len > 0 {
+                let at = cursor().map_or(len - 1, |at| at.saturating_sub(1));
+                cursor.set(Some(at));
+                input.set(history.read()[at].clone());
+            }
+        }
+        Key::ArrowDown => {
+            evt.prevent_default();
+            let next = cursor().map(|at| at + 1).filter(|at| *at < history.read().len());
+            cursor.set(next);
+            input.set(next.map(|at| history.read()[at].clone()).unwrap_or_default());
+        }
+        _ => {}
+    };
+
+    rsx! {
+        Panel { id: "console-area",
+            SpecCard { label: "CONSOLE:", value: "push, pop, inspect, len, burst, drain",
+                "Commands run against the same singletons as the buttons, default to the array, "
+                "and take a "
+                strong { "stack" }
+                " prefix for the LinkedStack. Results are also written to the Live Operation Log."
+            }
+
+            div { class: "console",
+                div { class: "console-scroll",
+                    div {
+                        for (i , line) in lines.read().iter().enumerate() {
+                            div { class: line.class(), key: "{i}", {line.text()} }
+                        }
+                    }
+                }
+                div { class: "console-prompt",
+                    span { {format!("{}>", target().label())} }
+                    input {
+                        class: "console-input-field",
+                        spellcheck: "false",
+                        autocomplete: "off",
+                        placeholder: "inspect 3 *= 2",
+                        value: "{input}",
+                        oninput: move |evt| input.set(evt.value()),
+                        onkeydown,
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn array(line: &str) -> Result<Command, String> {
+        parse(line, Target::Array)
+    }
+
+    #[test]
+    fn parses_every_command() {
+        assert_eq!(array("help"), Ok(Command::Help));
+        assert_eq!(array("use stack"), Ok(Command::Use(Target::Stack)));
+        assert_eq!(array("push 1 -2  3"), Ok(Command::Push(Target::Array, vec![1, -2, 3])));
+        assert_eq!(array("pop"), Ok(Command::Pop(Target::Array)));
+        assert_eq!(array("inspect 4"), Ok(Command::Inspect(4, None)));
+        assert_eq!(array("inspect 4 *= -3"), Ok(Command::Inspect(4, Some((AssignOp::Mul, -3)))));
+        assert_eq!(array("len"), Ok(Command::Len(Target::Array)));
+        assert_eq!(array("show"), Ok(Command::Show(Target::Array)));
+        assert_eq!(array("burst 4x25"), Ok(Command::Burst(Target::Array, 4, 25)));
+        assert_eq!(array("drain"), Ok(Command::Drain(Target::Array)));
+        assert_eq!(array("history"), Ok(Command::History));
+        assert_eq!(array("clear"), Ok(Command::Clear));
+    }
+
+    #[test]
+    fn a_prefix_overrides_the_default_target() {
+        assert_eq!(array("stack pop"), Ok(Command::Pop(Target::Stack)));
+        assert_eq!(parse("array len", Target::Stack), Ok(Command::Len(Target::Array)));
+        assert_eq!(parse("len", Target::Stack), Ok(Command::Len(Target::Stack)));
+        assert!(array("stack inspect 0").unwrap_err().contains("needs index access"));
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(array("").unwrap_err().contains("empty command"));
+        assert!(array("stack").unwrap_err().contains("empty command"));
+        assert!(array("frobnicate").unwrap_err().contains("unknown command `frobnicate`"));
+        assert!(array("push").unwrap_err().contains("usage: push"));
+        assert!(array("push 1 x").unwrap_err().contains("`x` is not a valid value"));
+        assert!(array("push 2147483648").unwrap_err().contains("not a valid value"));
+        assert!(array("inspect -1").unwrap_err().contains("not a valid index"));
+        assert!(array("inspect 1 ^= 2").unwrap_err().contains("unknown operator `^=`"));
+        assert!(array("inspect 1 +=").unwrap_err().contains("missing operand"));
+        assert_eq!(array("inspect 1 /= 0"), Err("division by zero".to_string()));
+        assert_eq!(array("inspect 1 %= 0"), Err("division by zero".to_string()));
+        assert!(array("use heap").unwrap_err().contains("usage: use"));
+        assert!(array("burst 4").unwrap_err().contains("usage: burst"));
+        assert!(array("burst 0x5").unwrap_err().contains("1-16 workers"));
+        assert!(array("burst 2x1001").unwrap_err().contains("1-1000 pushes"));
+        assert_eq!(array("pop now"), Err("unexpected `now` after `pop`".to_string()));
+    }
+
+    #[test]
+    fn assignments_wrap_like_the_array_ops() {
+        assert_eq!(AssignOp::Add.apply(i32::MAX, 1), i32::MIN);
+        assert_eq!(AssignOp::Sub.apply(i32::MIN, 1), i32::MAX);
+        assert_eq!(AssignOp::Mul.apply(i32::MAX, 2), -2);
+        assert_eq!(AssignOp::Div.apply(i32::MIN, -1), i32::MIN);
+        assert_eq!(AssignOp::Rem.apply(i32::MIN, -1), 0);
+        assert_eq!(AssignOp::Set.apply(5, 9), 9);
+    }
+
+    #[test]
+    fn completes_commands_and_targets() {
+        assert_eq!(
+            complete("dr"),
+            Completion {
+                line: "drain ".to_string(),
+                candidates: Vec::new(),
+            }
+        );
+        let ambiguous = complete("stack p");
+        assert_eq!(ambiguous.line, "stack p");
+        assert_eq!(ambiguous.candidates, vec!["pop", "push"]);
+        assert_eq!(complete("h").line, "h");
+        assert_eq!(complete("h").candidates, vec!["help", "history"]);
+        assert_eq!(complete("use s").line, "use stack ");
+        assert_eq!(complete("push 1").line, "push 1");
+    }
+}
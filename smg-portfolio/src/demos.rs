@@ -0,0 +1,432 @@
+//! The two singleton demos and the slot that embeds them in pages.
+
+use std::cell::Cell;
+
+use dioxus::prelude::*;
+use tracing::{debug, info_span, Instrument};
+
+use crate::chaos::{self, CellFault, ChaosPanel};
+use crate::pop_errors::{pop_with_retry, PopOp, DRAIN_RETRY, NO_RETRY};
+use crate::ui::{Button, Chip, Controls, LogView, Panel, SpecCard, Variant};
+use crate::{
+    array_pop, mutate_element, publish_snapshot, push_log, read_snapshot, resume, script, spawn_span, stack_pop,
+    stack_push, DemoEnv, Session,
+};
+
+const RSMG_CORE_CRATE: &str = "https://crates.io/crates/rsmg_core";
+
+/// The CONTIGUOUS_ARRAY controls, grid, log and fault injection panel.
+#[component]
+pub fn ArrayDemo(env: DemoEnv) -> Element {
+    let DemoEnv {
+        mut array_items,
+        race_log,
+        pop_errors,
+        ..
+    } = env;
+    let array_snapshot = array_items.read().clone();
+    let fault_report = chaos::report();
+
+    rsx! {
+        Panel { id: "array-area",
+            SpecCard {
+                label: "ENGINE:",
+                value: "rsmg_core::prim::array::ContiguousArray",
+                chips: rsx! { Chip { href: RSMG_CORE_CRATE, "crates.io" } },
+                "A "
+                strong { "true concurrency-first primitive" }
+                " that allows "
+                strong { "simultaneous index-based access" }
+                " and "
+                strong { "element-level mutations" }
+                " across all indices. Multiple workers can operate on different indices concurrently without blocking or corruption."
+            }
+
+            Controls {
+                // CONCURRENT MATRIX TRANSFORM - The star of the show
+                Button {
+                    variant: Variant::Burst,
+                    onclick: move |_| script::run_builtin(script::MATRIX_TRANSFORM, env),
+                    "MATRIX TRANSFORM (4 workers)"
+                }
+
+                // CONCURRENT PIPELINE - Producers + Transformers + Consumers
+                Button {
+                    variant: Variant::Race,
+                    onclick: move |_| script::run_builtin(script::PIPELINE, env),
+                    "CONCURRENT PIPELINE"
+                }
+
+                // CONCURRENT STATISTICS - Calculate stats on different sections simultaneously
+                Button {
+                    onclick: move |_| script::run_builtin(script::STATISTICS, env),
+                    "CONCURRENT STATISTICS"
+                }
+
+                // SEED - Create initial matrix (reduced to 32 elements)
+                Button {
+                    variant: Variant::Seed,
+                    onclick: move |_| script::run_builtin(script::SEED, env),
+                    "SEED MATRIX (32 elements)"
+                }
+
+                // RESET
+                Button {
+                    variant: Variant::Reset,
+                    onclick: move |_| {
+                        spawn(async move {
+                            let mut count = 0;
+                            loop {
+                                match pop_with_retry(PopOp::ArrayReset, DRAIN_RETRY, pop_errors, array_pop)
+                                    .await
+                                {
+                                    Ok(Some(val)) => {
+                                        trace!(value = val, "pop");
+                                        count += 1;
+                                        if count % 10 == 0 {
+                                            gloo_timers::future::TimeoutFuture::new(10).await;
+                                        }
+                                    }
+                                    Ok(None) => break,
+                                    Err(err) => {
+                                        push_log(race_log, format!("RESET stopped after {} elements ({})", count, err));
+                                        publish_snapshot(array_items);
+                                        return;
+                                    }
+                                }
+                            }
+                            array_items.set(vec![]);
+
+                            push_log(race_log, format!("Drained {} elements", count));
+                        }.instrument(spawn_span(info_span!("array_drain"))));
+                    },
+                    "RESET"
+                }
+            }
+
+            // Array Visualizer - Shows live concurrent operations
+            div { class: "array-visualizer",
+                div { class: "array-header",
+                    span { "Matrix Size: {array_snapshot.len()} elements" }
+                    span { " | Click any cell to mutate individually" }
+                }
+                div { class: "array-grid",
+                    for idx in 0..array_snapshot.len() {
+                        ArrayCell {
+                            idx,
+                            val: array_snapshot.get(idx).copied().unwrap_or(0),
+                            fault: chaos::cell_fault(idx),
+                            array_items,
+                            race_log,
+                        }
+                    }
+                }
+                if array_snapshot.is_empty() {
+                    div { class: "array-empty", "Matrix is empty. Click 'SEED MATRIX' to begin!" }
+                }
+            }
+
+            // Activity Log - Shows concurrent operation results in real-time
+            LogView {
+                title: "Live Operation Log",
+                empty: race_log.read().is_empty(),
+                placeholder: "No operations yet. Try the concurrent demos above!",
+                for (i , entry) in race_log.read().iter().enumerate() {
+                    div { class: "log-entry", key: "{i}", "{entry}" }
+                }
+            }
+
+            ChaosPanel {
+                report: fault_report,
+                len: array_snapshot.len(),
+                mode: chaos::mode(),
+                rate: chaos::rate(),
+            }
+
+            // Explanation Box
+            div { class: "demo-explanation",
+                h4 { "True Concurrency First Design" }
+                ul {
+                    li {
+                        strong { "MATRIX TRANSFORM: " }
+                        "4 workers hitting different sections of the matrix at once. "
+                        "Each worker runs its own transformation logic "
+                        strong { "simultaneously without ever blocking." }
+                    }
+                    li {
+                        strong { "CONCURRENT PIPELINE: " }
+                        "2 producers pushing, 1 transformer mutating, and 1 consumer popping—"
+                        strong { "all at the same time." }
+                        " It’s a literal demonstration of lock-free producer-consumer concurrency."
+                    }
+                    li {
+                        strong { "CONCURRENT STATISTICS: " }
+                        "4 workers crunching stats (sum, avg, min, max) across different chunks simultaneously. "
+                        "The global total is merged from the sections once all 4 have joined. "
+                        "Each read is atomic per element, but a section scan is not a snapshot: "
+                        "if another worker writes inside the section mid-scan, the result mixes old and new values "
+                        "and is reported as TORN. Run it alongside MATRIX TRANSFORM to see the difference."
+                    }
+                    li {
+                        strong { "FAULT INJECTION: " }
+                        "An early return tears only the element it touched. "
+                        "A panic inside the callback leaves that index locked and blocks every later push and pop, "
+                        "so wrap untrusted callbacks before they reach inspect_element."
+                    }
+                    li {
+                        strong { "SCRIPTS: " }
+                        "MATRIX TRANSFORM, CONCURRENT PIPELINE, CONCURRENT STATISTICS and SEED each run a built-in script. "
+                        "Open them in the SCRIPT EDITOR below to see exactly what they do, or to change it."
+                    }
+                    li {
+                        strong { "CONSOLE: " }
+                        "Type single operations such as "
+                        code { "push 42" }
+                        ", "
+                        code { "inspect 3 *= 2" }
+                        " or "
+                        code { "burst 4x25" }
+                        " and watch them land in the grid and the Live Operation Log."
+                    }
+                    li {
+                        strong { "CLICK CELLS: " }
+                        "Try clicking cells while the workers are running. "
+                        "Your manual mutations won't corrupt a thing because every index is independently accessible."
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ArrayCell(
+    idx: usize,
+    val: i32,
+    fault: CellFault,
+    mut array_items: Signal<Vec<i32>>,
+    race_log: Signal<Vec<String>>,
+) -> Element {
+    let (fault_class, fault_title) = match fault {
+        CellFault::None => ("", String::new()),
+        CellFault::Torn { before, intended, left } => (
+            "cell-torn",
+            format!("early return: {before} → {left}, intended {intended}"),
+        ),
+        CellFault::Poisoned { before, intended, left } => (
+            "cell-poisoned",
+            format!("panicked: {before} → {left}, intended {intended}; index locked"),
+        ),
+    };
+
+    rsx! {
+        div {
+            class: "array-cell {fault_class}",
+            title: "{fault_title}",
+            key: "{idx}",
+            onclick: move |_| {
+                let idx = idx;
+                spawn(async move {
+                    // Direct index mutation while other operations may be running
+                    mutate_element(
+                        idx,
+                        |v| {
+                            *v = (*v + 1) % 1000;
+                        },
+                    );
+                    // Update snapshot
+                    let snapshot = read_snapshot();
+                    let new_val = snapshot.get(idx).copied().unwrap_or(0);
+                    debug!(len = snapshot.len(), "snapshot published");
+                    array_items.set(snapshot);
+                    push_log(race_log, format!("User clicked index[{}] → {}", idx, new_val));
+                }.instrument(info_span!("cell_click", idx)));
+            },
+            div { class: "cell-index", "[{idx}]" }
+            div { class: "cell-value", "{val}" }
+        }
+    }
+}
+
+/// The MAGIC_DATA counter.
+#[component]
+pub fn StackDemo(env: DemoEnv) -> Element {
+    let DemoEnv {
+        mut stack_items,
+        race_log,
+        pop_errors,
+        ..
+    } = env;
+
+    let handle_pop = move |_| {
+        spawn(async move {
+            let popped = pop_with_retry(PopOp::StackPop, NO_RETRY, pop_errors, stack_pop).await;
+            debug!(result = ?popped, "stack pop");
+            if let Ok(Some(_)) = popped {
+                let new_len = stack_items.read().len().saturating_sub(1);
+
+                if new_len == 0 {
+                    stack_items.set(vec![]);
+                } else {
+                    stack_items.set(vec![new_len as i32; new_len]);
+                }
+            }
+        });
+    };
+
+    rsx! {
+        Panel { id: "magic-area",
+            SpecCard {
+                label: "ENGINE:",
+                value: "rsmg_core::prim::stack::LinkedStack",
+                chips: rsx! { Chip { href: RSMG_CORE_CRATE, "crates.io" } },
+                notes: rsx! {
+                    div { class: "bonus-challenge",
+                        "BONUS CHALLENGE: Try to make your browser go \"Aw snap!\""
+                    }
+                },
+                "This is a magical counter that grows in size as the counter increments. "
+                "The operation takes advantage of workers doing atomic ops in the background "
+                "and the renderer loop takes a snapshot of it."
+            }
+
+            Controls {
+                Button {
+                    onclick: move |_| {
+                        spawn(async move {
+                            let current_val = stack_items.read().len() as i32 + 1;
+                            let pushed = stack_push(current_val);
+                            debug!(value = current_val, result = ?pushed, "stack push");
+                            match pushed {
+                                Ok(()) => stack_items.set(vec![current_val; current_val as usize]),
+                                Err(err) => push_log(race_log, format!("PUSH {} refused ({})", current_val, err)),
+                            }
+                        });
+                    },
+                    "PUSH"
+                }
+
+                Button {
+                    variant: Variant::Burst,
+                    onclick: move |_| {
+                        let workers = 4;
+                        let per_worker = 25;
+                        let start_val = stack_items.read().len() as i32;
+
+                        spawn(async move {
+                            let refused = Cell::new(0);
+                            let tasks = (0..workers).map(|w| {
+                                let refused = &refused;
+                                async move {
+                                    for i in 1..=per_worker {
+                                        let current_val = start_val + (w * per_worker) + i;
+                                        let pushed = stack_push(current_val);
+                                        debug!(value = current_val, result = ?pushed, "stack push");
+                                        match pushed {
+                                            Ok(()) => stack_items.set(vec![current_val; current_val as usize]),
+                                            Err(_) => refused.set(refused.get() + 1),
+                                        }
+                                        gloo_timers::future::TimeoutFuture::new(0).await;
+                                    }
+                                }
+                                .instrument(spawn_span(info_span!("stack_burst_worker", worker_id = w)))
+                            });
+                            futures_util::future::join_all(tasks).await;
+                            if refused.get() > 0 {
+                                push_log(race_log, format!("BURST x100: {} pushes were refused", refused.get()));
+                            }
+                        });
+                    },
+                    "BURST x100"
+                }
+                Button { variant: Variant::Secondary, onclick: handle_pop, "POP" }
+
+                Button {
+                    variant: Variant::Reset,
+                    onclick: move |_| {
+                        spawn(async move {
+                            let before = stack_items.read().len();
+                            let mut dropped = 0;
+                            let drained = loop {
+                                match pop_with_retry(PopOp::StackDrain, DRAIN_RETRY, pop_errors, stack_pop)
+                                    .await
+                                {
+                                    Ok(Some(_)) => {
+                                        dropped += 1;
+                                        if dropped % 50 == 0 {
+                                            stack_items.set(vec![0; dropped % 10]);
+                                            gloo_timers::future::TimeoutFuture::new(1).await;
+                                        }
+                                    }
+                                    Ok(None) => break Ok(()),
+                                    Err(err) => break Err(err),
+                                }
+                            };
+                            match drained {
+                                Ok(()) => {
+                                    debug!(dropped, "stack drained");
+                                    stack_items.set(vec![]);
+                                }
+                                Err(err) => {
+                                    push_log(race_log, format!("DRAIN ALL stopped after {} nodes ({})", dropped, err));
+                                    // The nodes still on the stack stay on screen.
+                                    let left = before.saturating_sub(dropped);
+                                    stack_items.set(vec![left as i32; left]);
+                                }
+                            }
+                        }.instrument(spawn_span(info_span!("stack_drain"))));
+                    },
+                    "DRAIN ALL"
+                }
+            }
+
+            div { class: "stack-visualizer",
+                for (i , val) in stack_items.read().iter().enumerate() {
+                    div {
+                        class: "stack-node",
+                        key: "{val}-{i}",
+                        onclick: handle_pop,
+                        span { "{val}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A demo embedded in a project page or a post, on the shared `Session`.
+#[component]
+pub fn EmbeddedDemo(demo: resume::DemoEmbed) -> Element {
+    let Session { env, .. } = use_context::<Session>();
+
+    match demo {
+        resume::DemoEmbed::ContiguousArray => rsx! {
+            DemoSlot { area: "array-area", label: "ENGINE:", value: "rsmg_core::prim::array::ContiguousArray",
+                ArrayDemo { env }
+            }
+        },
+        resume::DemoEmbed::LinkedStack => rsx! {
+            DemoSlot { area: "magic-area", label: "ENGINE:", value: "rsmg_core::prim::stack::LinkedStack",
+                StackDemo { env }
+            }
+        },
+    }
+}
+
+/// `children` once the browser has hydrated the page; before that, and in
+/// the pre-rendered HTML, the section's spec line and a loading note under
+/// the same `area` id, so anchors and layout hold either way. Demos touch
+/// the singletons and timers, which only make sense in the browser.
+#[component]
+pub fn DemoSlot(area: &'static str, label: &'static str, value: &'static str, children: Element) -> Element {
+    let Session { hydrated, .. } = use_context::<Session>();
+    if hydrated() {
+        return children;
+    }
+
+    rsx! {
+        Panel { id: area, class: "demo-preview",
+            SpecCard { label, value, "Loading the interactive demo…" }
+        }
+    }
+}
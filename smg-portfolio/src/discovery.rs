@@ -0,0 +1,278 @@
+//! `feed.xml`, `sitemap.xml`, `robots.txt`, `404.html` and the link preview
+//! card for the deployed site.
+//!
+//! The web build cannot write files, so a native run of the same binary
+//! produces them after `dx build`:
+//!
+//! ```text
+//! cargo run -- site-files dist
+//! ```
+//!
+//! URLs come from `site::SITE`, pages from `routes::site_routes`, so a new
+//! post or project shows up without touching this module. Drafts never do.
+//! Crawlers only read `robots.txt` at the root of a host; under a base path
+//! it is a template for the host's own file. `404.html` is the SPA fallback
+//! for paths without a pre-rendered page; see `routes`.
+
+use std::path::{Path, PathBuf};
+
+use crate::blog::{Post, POSTS};
+use crate::browser::DEEP_LINK_KEY;
+use crate::resume::RESUME;
+use crate::routes::{self, Route};
+use crate::scenario::BASE_PATH;
+use crate::site::{Site, CARD_FILE, FEED_FILE, SITE};
+
+pub const SITEMAP_FILE: &str = "sitemap.xml";
+pub const ROBOTS_FILE: &str = "robots.txt";
+pub const NOT_FOUND_FILE: &str = "404.html";
+const CARD: &[u8] = include_bytes!("../assets/og-card.png");
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn published(posts: &[Post]) -> impl Iterator<Item = &Post> {
+    posts.iter().filter(|post| !post.draft)
+}
+
+/// An Atom feed of the published posts, newest first; `None` without any.
+pub fn feed(site: &Site, author: &str, posts: &[Post]) -> Option<String> {
+    let updated = published(posts).map(|post| post.date.as_str()).max()?;
+    let writing = site.absolute(&Route::Writing {}.to_string());
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml += "<feed xmlns=\"http://www.w3.org/2005/Atom\">\n";
+    xml += &format!("  <title>{}</title>\n", escape(&site.title));
+    xml += &format!("  <subtitle>{}</subtitle>\n", escape(&site.description));
+    xml += &format!("  <link rel=\"self\" href=\"{}\"/>\n", escape(&site.absolute(&format!("/{FEED_FILE}"))));
+    xml += &format!("  <link rel=\"alternate\" href=\"{}\"/>\n", escape(&writing));
+    xml += &format!("  <id>{}</id>\n", escape(&site.url));
+    xml += &format!("  <updated>{updated}T00:00:00Z</updated>\n");
+    xml += &format!("  <author><name>{}</name></author>\n", escape(author));
+    for post in published(posts) {
+        let url = escape(&site.absolute(&Route::WritingPost { slug: post.slug.clone() }.to_string()));
+        xml += "  <entry>\n";
+        xml += &format!("    <title>{}</title>\n", escape(&post.title));
+        xml += &format!("    <link rel=\"alternate\" href=\"{url}\"/>\n");
+        xml += &format!("    <id>{url}</id>\n");
+        xml += &format!("    <updated>{}T00:00:00Z</updated>\n", post.date);
+        if let Some(summary) = &post.summary {
+            xml += &format!("    <summary>{}</summary>\n", escape(summary));
+        }
+        for tag in &post.tags {
+            xml += &format!("    <category term=\"{}\"/>\n", escape(tag));
+        }
+        xml += "  </entry>\n";
+    }
+    xml += "</feed>\n";
+    Some(xml)
+}
+
+/// Every page `site_routes` knows, with the post date as `lastmod`.
+pub fn sitemap(site: &Site, posts: &[Post]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml += "<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n";
+    for route in routes::site_routes() {
+        let lastmod = match &route {
+            Route::WritingPost { slug } => published(posts).find(|post| post.slug == *slug).map(|post| &post.date),
+            _ => None,
+        };
+        xml += &format!("  <url><loc>{}</loc>", escape(&site.absolute(&route.to_string())));
+        if let Some(date) = lastmod {
+            xml += &format!("<lastmod>{date}</lastmod>");
+        }
+        xml += "</url>\n";
+    }
+    xml += "</urlset>\n";
+    xml
+}
+
+pub fn robots(site: &Site) -> String {
+    let base = site.url.split_once("://").and_then(|(_, rest)| rest.find('/').map(|at| &rest[at..]));
+    format!(
+        "User-agent: *\nAllow: {}\nSitemap: {}\n",
+        base.unwrap_or("/"),
+        site.absolute(&format!("/{SITEMAP_FILE}"))
+    )
+}
+
+/// What GitHub Pages serves for a path no pre-rendered page covers: it
+/// leaves the path under `DEEP_LINK_KEY` and loads the app at the base path,
+/// which routes there after hydrating. Without script or storage it stays a
+/// plain not-found page.
+pub fn not_found(site: &Site) -> String {
+    let title = escape(&site.title);
+    let mut html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    html += "  <meta charset=\"utf-8\">\n";
+    html += &format!("  <title>Not found · {title}</title>\n");
+    html += "  <script>\n";
+    html += &format!("    var base = {BASE_PATH:?}, path = location.pathname;\n");
+    // The app itself missing must not send the browser round in circles.
+    html += "    if (path.indexOf(base) === 0 && path.length > base.length) {\n";
+    html += "      try {\n";
+    html += &format!("        sessionStorage.setItem({DEEP_LINK_KEY:?}, path.slice(base.length - 1));\n");
+    html += "        location.replace(base);\n";
+    html += "      } catch (err) {}\n";
+    html += "    }\n";
+    html += "  </script>\n";
+    html += "</head>\n<body>\n  <h1>404</h1>\n";
+    html += &format!("  <p>Nothing lives here. <a href=\"{}\">Back to {title}</a></p>\n", escape(&site.url));
+    html += "</body>\n</html>\n";
+    html
+}
+
+/// `Some(dir)` when the binary was started as `smg-portfolio site-files DIR`.
+pub fn site_files_dir() -> Option<PathBuf> {
+    let mut args = std::env::args().skip(1);
+    match (args.next().as_deref(), args.next()) {
+        (Some("site-files"), Some(dir)) => Some(dir.into()),
+        _ => None,
+    }
+}
+
+/// Writes the files into `dir`, e.g. the `dist` folder that gets
+/// deployed, and returns their paths.
+pub fn write(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let site = SITE.as_ref()?;
+    let posts = POSTS.as_ref()?;
+    let author = &RESUME.as_ref()?.basics.name;
+
+    let mut files = vec![
+        (SITEMAP_FILE, sitemap(site, posts).into_bytes()),
+        (ROBOTS_FILE, robots(site).into_bytes()),
+        (NOT_FOUND_FILE, not_found(site).into_bytes()),
+        (CARD_FILE, CARD.to_vec()),
+    ];
+    if let Some(feed) = feed(site, author, posts) {
+        files.push((FEED_FILE, feed.into_bytes()));
+    }
+    std::fs::create_dir_all(dir).map_err(|err| format!("{}: {err}", dir.display()))?;
+    let mut written = Vec::new();
+    for (name, contents) in files {
+        let path = dir.join(name);
+        std::fs::write(&path, contents).map_err(|err| format!("{}: {err}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn site() -> Site {
+        Site {
+            url: "https://example.org/portfolio/".to_string(),
+            title: "Tom & Jerry's".to_string(),
+            description: "<notes>".to_string(),
+        }
+    }
+
+    fn post(slug: &str, date: &str, draft: bool) -> Post {
+        Post {
+            slug: slug.to_string(),
+            title: format!("{slug} \"title\""),
+            date: date.to_string(),
+            summary: Some("a < b".to_string()),
+            tags: vec!["rust".to_string()],
+            draft,
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn escapes_markup() {
+        assert_eq!(
+            escape("<a href=\"x\">Tom & 'Jerry'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &apos;Jerry&apos;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn feed_lists_published_posts_only() {
+        assert_eq!(feed(&site(), "Me", &[post("draft", "2026-01-01", true)]), None);
+
+        let posts = [
+            post("newer", "2026-02-01", false),
+            post("draft", "2026-03-01", true),
+            post("older", "2025-12-24", false),
+        ];
+        let xml = feed(&site(), "Me", &posts).unwrap();
+        assert!(xml.contains("<title>Tom &amp; Jerry&apos;s</title>"), "{xml}");
+        assert!(xml.contains("<link rel=\"self\" href=\"https://example.org/portfolio/feed.xml\"/>"), "{xml}");
+        assert!(xml.contains("<updated>2026-02-01T00:00:00Z</updated>\n  <author>"), "{xml}");
+        assert!(xml.contains("<id>https://example.org/portfolio/writing/newer</id>"), "{xml}");
+        assert!(xml.contains("<summary>a &lt; b</summary>"), "{xml}");
+        assert!(!xml.contains("draft"), "{xml}");
+        assert_eq!(xml.matches("<entry>").count(), 2);
+        assert!(xml.find("newer").unwrap() < xml.find("older").unwrap());
+        assert!(xml.ends_with("</feed>\n"));
+    }
+
+    #[test]
+    fn sitemap_lists_every_route_once() {
+        let posts = POSTS.as_ref().unwrap();
+        let xml = sitemap(&site(), posts);
+        let routes = routes::site_routes();
+        assert_eq!(xml.matches("<url>").count(), routes.len());
+        for route in routes {
+            let loc = format!("<loc>{}</loc>", site().absolute(&route.to_string()));
+            assert_eq!(xml.matches(&loc).count(), 1, "{loc}");
+        }
+        for post in published(posts) {
+            let url = site().absolute(&Route::WritingPost { slug: post.slug.clone() }.to_string());
+            assert!(xml.contains(&format!("<loc>{url}</loc><lastmod>{}</lastmod>", post.date)), "{xml}");
+        }
+    }
+
+    #[test]
+    fn robots_allows_the_base_path() {
+        assert_eq!(
+            robots(&site()),
+            "User-agent: *\nAllow: /portfolio/\nSitemap: https://example.org/portfolio/sitemap.xml\n"
+        );
+        let root = Site {
+            url: "https://example.org".to_string(),
+            ..site()
+        };
+        assert!(robots(&root).contains("Allow: /\n"));
+    }
+
+    #[test]
+    fn not_found_hands_the_path_to_the_app() {
+        let html = not_found(&site());
+        assert!(html.contains("<title>Not found · Tom &amp; Jerry&apos;s</title>"), "{html}");
+        assert!(html.contains("var base = \"/portfolio/\""), "{html}");
+        assert!(html.contains(&format!("sessionStorage.setItem(\"{DEEP_LINK_KEY}\", ")), "{html}");
+        assert!(html.contains("location.replace(base)"), "{html}");
+        assert!(html.contains("href=\"https://example.org/portfolio/\""), "{html}");
+    }
+
+    #[test]
+    fn writes_every_file() {
+        let dir = std::env::temp_dir().join(format!("smg-portfolio-site-files-{}", std::process::id()));
+        let written = write(&dir).unwrap();
+        let mut names: Vec<_> = written.iter().map(|path| path.file_name().unwrap().to_str().unwrap()).collect();
+        names.sort_unstable();
+        let mut expected = vec![CARD_FILE, NOT_FOUND_FILE, ROBOTS_FILE, SITEMAP_FILE];
+        if published(POSTS.as_ref().unwrap()).next().is_some() {
+            expected.push(FEED_FILE);
+        }
+        expected.sort_unstable();
+        assert_eq!(names, expected);
+        assert_eq!(std::fs::read(dir.join(CARD_FILE)).unwrap(), CARD);
+        assert!(std::fs::read_to_string(dir.join(SITEMAP_FILE)).unwrap().starts_with("<?xml"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
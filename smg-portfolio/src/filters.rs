@@ -0,0 +1,540 @@
+//! Image filters over pixels stored in a `ContiguousArray<u32>`.
+//!
+//! An image is decoded by the browser, scaled to fit `MAX_WIDTH` ×
+//! `MAX_HEIGHT` and pushed into the front buffer, one RGBA pixel per element.
+//! A filter pass cuts the image into `TILE` × `TILE` tiles and `WORKERS`
+//! workers pull tiles from a shared counter, so fast workers take more of
+//! them. Kernels read neighbours from the front buffer and write the back
+//! buffer, which becomes the front once every worker has joined; each
+//! finished tile is drawn to the canvas straight away.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+use dioxus::prelude::*;
+use rsmg_core::prim::array::ContiguousArray;
+use tracing::{debug, info, info_span, warn, Instrument};
+
+use crate::matrix::Matrix2D;
+use crate::pop_errors::{PopErrorLog, PopOp};
+use crate::ui::{Button, Panel, SpecCard, Variant};
+
+static BUFFER_A: LazyLock<ContiguousArray<u32>> = LazyLock::new(ContiguousArray::new);
+static BUFFER_B: LazyLock<ContiguousArray<u32>> = LazyLock::new(ContiguousArray::new);
+static FRONT_IS_A: AtomicBool = AtomicBool::new(true);
+static WIDTH: AtomicUsize = AtomicUsize::new(0);
+static HEIGHT: AtomicUsize = AtomicUsize::new(0);
+/// Pixels as loaded, for RESET.
+static ORIGINAL: LazyLock<Mutex<Vec<u32>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+const FILTER_SAMPLE: Asset = asset!("/assets/filter-sample.png");
+
+pub const CANVAS_ID: &str = "filter-canvas";
+pub const MAX_WIDTH: usize = 480;
+pub const MAX_HEIGHT: usize = 360;
+pub const TILE: usize = 32;
+pub const WORKERS: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Filter {
+    Grayscale,
+    BoxBlur,
+    EdgeDetect,
+}
+
+impl Filter {
+    pub const ALL: [Filter; 3] = [Filter::Grayscale, Filter::BoxBlur, Filter::EdgeDetect];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Filter::Grayscale => "grayscale",
+            Filter::BoxBlur => "box blur 3×3",
+            Filter::EdgeDetect => "edge detect (Sobel)",
+        }
+    }
+
+    /// New value of `(row, col)`, computed from `src` only.
+    fn pixel(self, src: Matrix2D<u32>, row: usize, col: usize) -> u32 {
+        let (r, c) = (row as isize, col as isize);
+        match self {
+            Filter::Grayscale => {
+                let [_, _, _, a] = channels(src.get(row, col));
+                let y = luma(src.get(row, col));
+                pack([y, y, y, a])
+            }
+            Filter::BoxBlur => {
+                let mut sum = [0u32; 4];
+                for dr in -1..=1 {
+                    for dc in -1..=1 {
+                        for (total, channel) in sum.iter_mut().zip(channels(src.get_clamped(r + dr, c + dc))) {
+                            *total += channel as u32;
+                        }
+                    }
+                }
+                pack(sum.map(|total| (total / 9) as u8))
+            }
+            Filter::EdgeDetect => {
+                const GX: [[i32; 3]; 3] = [[-1, 0, 1], [-2, 0, 2], [-1, 0, 1]];
+                const GY: [[i32; 3]; 3] = [[-1, -2, -1], [0, 0, 0], [1, 2, 1]];
+                let (mut gx, mut gy) = (0, 0);
+                for (i, dr) in (-1..=1).enumerate() {
+                    for (j, dc) in (-1..=1).enumerate() {
+                        let y = luma(src.get_clamped(r + dr, c + dc)) as i32;
+                        gx += GX[i][j] * y;
+                        gy += GY[i][j] * y;
+                    }
+                }
+                let magnitude = ((gx * gx + gy * gy) as f64).sqrt().min(255.0) as u8;
+                pack([magnitude, magnitude, magnitude, 255])
+            }
+        }
+    }
+}
+
+/// RGBA bytes in canvas order.
+fn channels(pixel: u32) -> [u8; 4] {
+    pixel.to_le_bytes()
+}
+
+fn pack(rgba: [u8; 4]) -> u32 {
+    u32::from_le_bytes(rgba)
+}
+
+fn luma(pixel: u32) -> u8 {
+    let [r, g, b, _] = channels(pixel);
+    ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FilterState {
+    pub source: Option<String>,
+    pub width: usize,
+    pub height: usize,
+    pub filter: Option<Filter>,
+    pub tiles_done: usize,
+    pub tiles_total: usize,
+    pub tiles_per_worker: Vec<usize>,
+    pub elapsed_ms: Option<f64>,
+    /// Filters applied since the image was loaded or reset.
+    pub passes: usize,
+    pub error: Option<String>,
+    pub running: bool,
+}
+
+impl FilterState {
+    pub fn pixels(&self) -> usize {
+        self.width * self.height
+    }
+}
+
+fn dimensions() -> (usize, usize) {
+    (WIDTH.load(Ordering::Acquire), HEIGHT.load(Ordering::Acquire))
+}
+
+fn buffers() -> (Matrix2D<u32>, Matrix2D<u32>) {
+    let (width, height) = dimensions();
+    let a = Matrix2D::new(&BUFFER_A, height, width);
+    let b = Matrix2D::new(&BUFFER_B, height, width);
+    if FRONT_IS_A.load(Ordering::Acquire) {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Pushes `pixels` into the front buffer and draws them. Nothing is drawn if
+/// a buffer could not be emptied first.
+async fn install(pixels: &[u32], width: usize, height: usize, pop_errors: Signal<PopErrorLog>) -> Result<(), String> {
+    WIDTH.store(width, Ordering::Release);
+    HEIGHT.store(height, Ordering::Release);
+    FRONT_IS_A.store(true, Ordering::Release);
+    let (front, back) = buffers();
+    front
+        .fill_with(PopOp::FilterInstall, pop_errors, |row, col| pixels[row * width + col])
+        .await
+        .map_err(|err| err.to_string())?;
+    back.fill_with(PopOp::FilterInstall, pop_errors, |_, _| 0).await.map_err(|err| err.to_string())?;
+    canvas::resize(width, height);
+    canvas::draw(pixels, 0, 0, width, height);
+    debug!(width, height, pixels = pixels.len(), "image installed");
+    Ok(())
+}
+
+async fn loaded(
+    mut state: Signal<FilterState>,
+    source: String,
+    decoded: Result<(Vec<u32>, usize, usize), String>,
+    pop_errors: Signal<PopErrorLog>,
+) {
+    let decoded = match decoded {
+        Ok((pixels, width, height)) => {
+            install(&pixels, width, height, pop_errors).await.map(|()| (pixels, width, height))
+        }
+        Err(err) => Err(err),
+    };
+    match decoded {
+        Ok((pixels, width, height)) => {
+            *ORIGINAL.lock().unwrap() = pixels;
+            info!(%source, width, height, "image loaded");
+            state.set(FilterState {
+                source: Some(source),
+                width,
+                height,
+                ..FilterState::default()
+            });
+        }
+        Err(err) => {
+            warn!(%source, %err, "image failed to load");
+            state.write().error = Some(format!("{source}: {err}"));
+        }
+    }
+}
+
+pub async fn load_sample(state: Signal<FilterState>, pop_errors: Signal<PopErrorLog>) {
+    let decoded = canvas::decode_url(&FILTER_SAMPLE.to_string()).await;
+    loaded(state, "bundled sample".to_string(), decoded, pop_errors).await;
+}
+
+pub async fn load_file(name: String, bytes: &[u8], state: Signal<FilterState>, pop_errors: Signal<PopErrorLog>) {
+    let decoded = canvas::decode_bytes(bytes).await;
+    loaded(state, name, decoded, pop_errors).await;
+}
+
+/// Restores the pixels as loaded.
+pub async fn reset(mut state: Signal<FilterState>, pop_errors: Signal<PopErrorLog>) {
+    let original = ORIGINAL.lock().unwrap().clone();
+    let (width, height) = dimensions();
+    if original.len() != width * height {
+        return;
+    }
+    if let Err(err) = install(&original, width, height, pop_errors).await {
+        warn!(%err, "filter reset failed");
+        state.write().error = Some(format!("reset failed: {err}"));
+        return;
+    }
+    let mut current = state.write();
+    current.passes = 0;
+    current.filter = None;
+    current.tiles_done = 0;
+    current.elapsed_ms = None;
+}
+
+/// Runs one filter pass over the front buffer with `WORKERS` tile workers.
+pub async fn apply(filter: Filter, mut state: Signal<FilterState>) {
+    let (width, height) = dimensions();
+    if width == 0 || height == 0 {
+        return;
+    }
+    let (front, back) = buffers();
+    let tiles_x = width.div_ceil(TILE);
+    let tiles_total = tiles_x * height.div_ceil(TILE);
+    {
+        let mut current = state.write();
+        current.filter = Some(filter);
+        current.tiles_done = 0;
+        current.tiles_total = tiles_total;
+        current.tiles_per_worker = vec![0; WORKERS];
+        current.elapsed_ms = None;
+        current.error = None;
+        current.running = true;
+    }
+    info!(filter = filter.label(), tiles_total, "filter pass started");
+    let started = canvas::now_ms();
+
+    // The tile queue; workers only touch it between awaits.
+    let next_tile = Cell::new(0);
+    let next_tile = &next_tile;
+    let workers = (0..WORKERS).map(|worker| {
+        async move {
+            loop {
+                let tile = next_tile.get();
+                if tile >= tiles_total {
+                    break;
+                }
+                next_tile.set(tile + 1);
+
+                let (x, y) = ((tile % tiles_x) * TILE, (tile / tiles_x) * TILE);
+                let (w, h) = (TILE.min(width - x), TILE.min(height - y));
+                let mut out = Vec::with_capacity(w * h);
+                for row in y..y + h {
+                    for col in x..x + w {
+                        let pixel = filter.pixel(front, row, col);
+                        back.set(row, col, pixel);
+                        out.push(pixel);
+                    }
+                }
+                canvas::draw(&out, x, y, w, h);
+                {
+                    let mut current = state.write();
+                    current.tiles_done += 1;
+                    current.tiles_per_worker[worker] += 1;
+                }
+                gloo_timers::future::TimeoutFuture::new(0).await;
+            }
+        }
+        .instrument(info_span!("filter_worker", worker))
+    });
+    futures_util::future::join_all(workers).await;
+
+    FRONT_IS_A.fetch_xor(true, Ordering::AcqRel);
+    let elapsed = canvas::now_ms() - started;
+    info!(filter = filter.label(), elapsed_ms = elapsed, "filter pass joined");
+    let mut current = state.write();
+    current.elapsed_ms = Some(elapsed);
+    current.passes += 1;
+    current.running = false;
+}
+
+/// Largest size within `MAX_WIDTH` × `MAX_HEIGHT` with the same aspect ratio.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn fit(width: usize, height: usize) -> (usize, usize) {
+    let scale = (MAX_WIDTH as f64 / width as f64).min(MAX_HEIGHT as f64 / height as f64).min(1.0);
+    (((width as f64 * scale) as usize).max(1), ((height as f64 * scale) as usize).max(1))
+}
+
+#[cfg(target_arch = "wasm32")]
+mod canvas {
+    use wasm_bindgen::{Clamped, JsCast, JsValue};
+    use web_sys::{Blob, CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement, ImageData, Url};
+
+    fn context(canvas: &HtmlCanvasElement) -> Result<CanvasRenderingContext2d, JsValue> {
+        canvas
+            .get_context("2d")?
+            .ok_or_else(|| JsValue::from_str("no 2d context"))?
+            .dyn_into::<CanvasRenderingContext2d>()
+            .map_err(JsValue::from)
+    }
+
+    fn page_canvas() -> Option<HtmlCanvasElement> {
+        web_sys::window()?
+            .document()?
+            .get_element_by_id(super::CANVAS_ID)?
+            .dyn_into::<HtmlCanvasElement>()
+            .ok()
+    }
+
+    /// Loads `url` into an image, scales it onto an offscreen canvas and
+    /// reads the pixels back.
+    pub async fn decode_url(url: &str) -> Result<(Vec<u32>, usize, usize), String> {
+        let decode = async {
+            let image = HtmlImageElement::new()?;
+            image.set_src(url);
+            wasm_bindgen_futures::JsFuture::from(image.decode()).await?;
+            let (width, height) = super::fit(image.natural_width() as usize, image.natural_height() as usize);
+
+            let document = web_sys::window().and_then(|w| w.document()).ok_or("no document")?;
+            let scratch = document.create_element("canvas")?.dyn_into::<HtmlCanvasElement>()?;
+            scratch.set_width(width as u32);
+            scratch.set_height(height as u32);
+            let ctx = context(&scratch)?;
+            ctx.draw_image_with_html_image_element_and_dw_and_dh(&image, 0.0, 0.0, width as f64, height as f64)?;
+            let data = ctx.get_image_data(0.0, 0.0, width as f64, height as f64)?.data();
+            let pixels = data
+                .0
+                .chunks_exact(4)
+                .map(|rgba| u32::from_le_bytes([rgba[0], rgba[1], rgba[2], rgba[3]]))
+                .collect();
+            Ok::<_, JsValue>((pixels, width, height))
+        };
+        decode.await.map_err(|err| format!("{err:?}"))
+    }
+
+    pub async fn decode_bytes(bytes: &[u8]) -> Result<(Vec<u32>, usize, usize), String> {
+        let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+        let blob = Blob::new_with_u8_array_sequence(&parts).map_err(|err| format!("{err:?}"))?;
+        let url = Url::create_object_url_with_blob(&blob).map_err(|err| format!("{err:?}"))?;
+        let decoded = decode_url(&url).await;
+        let _ = Url::revoke_object_url(&url);
+        decoded
+    }
+
+    pub fn resize(width: usize, height: usize) {
+        if let Some(canvas) = page_canvas() {
+            canvas.set_width(width as u32);
+            canvas.set_height(height as u32);
+        }
+    }
+
+    /// Puts a `width` × `height` block of pixels at `(x, y)`.
+    pub fn draw(pixels: &[u32], x: usize, y: usize, width: usize, height: usize) {
+        let Some(canvas) = page_canvas() else {
+            return;
+        };
+        let bytes: Vec<u8> = pixels.iter().flat_map(|pixel| pixel.to_le_bytes()).collect();
+        let drawn = ImageData::new_with_u8_clamped_array_and_sh(Clamped(&bytes), width as u32, height as u32)
+            .and_then(|data| context(&canvas)?.put_image_data(&data, x as f64, y as f64));
+        if let Err(err) = drawn {
+            tracing::warn!(?err, "canvas draw failed");
+        }
+    }
+
+    pub fn now_ms() -> f64 {
+        js_sys::Date::now()
+    }
+}
+
+/// Native builds have no canvas to decode or draw with; the filters still
+/// run if pixels get installed some other way.
+#[cfg(not(target_arch = "wasm32"))]
+mod canvas {
+    use std::sync::LazyLock;
+    use std::time::Instant;
+
+    static STARTED: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+    pub async fn decode_url(_url: &str) -> Result<(Vec<u32>, usize, usize), String> {
+        Err("image decoding needs the browser canvas; run the web build".to_string())
+    }
+
+    pub async fn decode_bytes(bytes: &[u8]) -> Result<(Vec<u32>, usize, usize), String> {
+        decode_url(&format!("{} bytes", bytes.len())).await
+    }
+
+    pub fn resize(_width: usize, _height: usize) {}
+
+    pub fn draw(_pixels: &[u32], _x: usize, _y: usize, _width: usize, _height: usize) {}
+
+    pub fn now_ms() -> f64 {
+        STARTED.elapsed().as_secs_f64() * 1000.0
+    }
+}
+
+/// Grayscale, blur and edge filters on a `ContiguousArray<u32>` of pixels.
+#[component]
+pub fn FilterDemo(pop_errors: Signal<PopErrorLog>) -> Element {
+    let state = use_signal(FilterState::default);
+    let mut filter = use_signal(|| Filter::BoxBlur);
+    let current = state.read();
+    let loaded = current.pixels() > 0;
+
+    rsx! {
+        Panel { id: "filter-area",
+            SpecCard { label: "PIXELS:", value: "ContiguousArray<u32>, one RGBA pixel per element",
+                "Load the bundled sample or an image of your own (scaled to fit {MAX_WIDTH}×{MAX_HEIGHT}). "
+                "{WORKERS} workers pull {TILE}×{TILE} tiles from a shared queue, "
+                "read neighbours from one buffer, write the other, and draw each tile as soon as it is done."
+            }
+
+            div { class: "trace-toolbar",
+                Button {
+                    variant: Variant::Seed,
+                    disabled: current.running,
+                    onclick: move |_| {
+                        spawn(load_sample(state, pop_errors));
+                    },
+                    "LOAD SAMPLE"
+                }
+                label { class: "btn btn-seed filter-file",
+                    "OPEN IMAGE…"
+                    input {
+                        r#type: "file",
+                        accept: "image/*",
+                        disabled: current.running,
+                        onchange: move |evt| {
+                            let Some(file) = evt.files().into_iter().next() else {
+                                return;
+                            };
+                            spawn(async move {
+                                match file.read_bytes().await {
+                                    Ok(bytes) => load_file(file.name(), &bytes, state, pop_errors).await,
+                                    Err(err) => {
+                                        let mut state = state;
+                                        state.write().error = Some(format!("{}: {err}", file.name()));
+                                    }
+                                }
+                            });
+                        },
+                    }
+                }
+                label {
+                    "Filter "
+                    select {
+                        disabled: current.running,
+                        onchange: move |evt| {
+                            let picked = Filter::ALL
+                                .into_iter()
+                                .find(|candidate| candidate.label() == evt.value())
+                                .unwrap_or(Filter::BoxBlur);
+                            filter.set(picked);
+                        },
+                        for candidate in Filter::ALL {
+                            option {
+                                value: "{candidate.label()}",
+                                selected: *filter.read() == candidate,
+                                "{candidate.label()}"
+                            }
+                        }
+                    }
+                }
+                Button {
+                    variant: Variant::Burst,
+                    loading: current.running,
+                    disabled: !loaded,
+                    onclick: move |_| {
+                        spawn(apply(filter(), state));
+                    },
+                    if current.running { "FILTERING..." } else { "APPLY" }
+                }
+                Button {
+                    variant: Variant::Reset,
+                    disabled: current.running || current.passes == 0,
+                    onclick: move |_| {
+                        spawn(reset(state, pop_errors));
+                    },
+                    "RESET"
+                }
+            }
+
+            if let Some(err) = &current.error {
+                div { class: "array-empty outcome-different", "{err}" }
+            }
+            if loaded {
+                div { class: "array-header",
+                    span { "{current.source.as_deref().unwrap_or_default()} · {current.width}×{current.height} = {current.pixels()} elements" }
+                    if current.passes > 0 {
+                        span { " | {current.passes} pass(es)" }
+                    }
+                }
+            } else {
+                div { class: "array-empty", "No image yet. Load the sample or open a file." }
+            }
+            // Sized by `filters` directly; rsx never sets width/height, so
+            // re-renders don't clear the drawing.
+            canvas { id: CANVAS_ID, class: if loaded { "filter-canvas" } else { "filter-canvas hidden" } }
+
+            if let Some(applied) = current.filter {
+                ul { class: "chaos-stats",
+                    li { "{applied.label()}: {current.tiles_done} of {current.tiles_total} tiles" }
+                    li { "tiles per worker: {current.tiles_per_worker:?}" }
+                    if let Some(elapsed) = current.elapsed_ms {
+                        li { class: "outcome-hit", "joined after {elapsed:.0} ms" }
+                    }
+                }
+            }
+
+            div { class: "demo-explanation",
+                h4 { "A data-parallel workload" }
+                ul {
+                    li {
+                        strong { "TILES: " }
+                        "workers take the next tile when they finish one, so the split adapts to uneven work. "
+                        "The tiles-per-worker counts show how it came out."
+                    }
+                    li {
+                        strong { "TWO BUFFERS: " }
+                        "blur and edge detection read a 3×3 neighbourhood. Writing in place would let a tile see "
+                        "pixels its neighbour already filtered, so results go to the back buffer and the buffers "
+                        "swap after the join."
+                    }
+                    li {
+                        strong { "SCALE: " }
+                        "the sample is 480×270, about 130k elements, each read up to nine times per pass through "
+                        strong { "inspect_element" }
+                        "."
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,347 @@
+//! Classic parallel algorithms run as worker sets on `CONTIGUOUS_ARRAY`.
+//!
+//! Every algorithm is a sequence of phases. Within a phase the tasks are cut
+//! into stripes, one worker per stripe, and the phase ends at a barrier (all
+//! workers joined) before the next one reads anything. The input is captured
+//! before the first phase and the sequential result is checked against what
+//! the workers left in the array, so a concurrent MATRIX TRANSFORM or PIPELINE
+//! shows up as a failed check instead of a wrong answer.
+
+use dioxus::prelude::*;
+use tracing::{debug, info, info_span, warn, Instrument};
+
+use crate::ui::{Button, Panel, SpecCard, Variant};
+use crate::{mutate_element, publish_snapshot, read_element, read_snapshot};
+
+pub const WORKERS: usize = 4;
+/// Pause after each barrier so the grid can show the phase.
+const PHASE_DELAY_MS: u32 = 120;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    PrefixSum,
+    OddEvenSort,
+    TreeReduction,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 3] = [Algorithm::PrefixSum, Algorithm::OddEvenSort, Algorithm::TreeReduction];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Algorithm::PrefixSum => "prefix sum",
+            Algorithm::OddEvenSort => "odd-even sort",
+            Algorithm::TreeReduction => "tree reduction",
+        }
+    }
+
+    /// Barriers a run over `len` elements goes through.
+    pub fn phases(self, len: usize) -> usize {
+        let strides = strides(len).count();
+        match self {
+            // A read phase and a write phase per stride.
+            Algorithm::PrefixSum => 2 * strides,
+            Algorithm::OddEvenSort => len,
+            Algorithm::TreeReduction => strides,
+        }
+    }
+
+    /// What one thread would compute from the same input.
+    fn sequential(self, input: &[i32]) -> Vec<i32> {
+        match self {
+            Algorithm::PrefixSum => input
+                .iter()
+                .scan(0i32, |sum, v| {
+                    *sum = sum.wrapping_add(*v);
+                    Some(*sum)
+                })
+                .collect(),
+            Algorithm::OddEvenSort => {
+                let mut sorted = input.to_vec();
+                sorted.sort_unstable();
+                sorted
+            }
+            Algorithm::TreeReduction => vec![input.iter().fold(0, |sum, v| sum.wrapping_add(*v))],
+        }
+    }
+}
+
+/// 1, 2, 4, ... below `len`.
+fn strides(len: usize) -> impl Iterator<Item = usize> {
+    std::iter::successors(Some(1usize), |s| s.checked_mul(2)).take_while(move |s| *s < len)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub expected: Vec<i32>,
+    pub actual: Vec<i32>,
+    /// Indices where the parallel result differs from the sequential one.
+    pub mismatches: Vec<usize>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GalleryState {
+    pub algorithm: Option<Algorithm>,
+    pub values: Vec<i32>,
+    pub phase: usize,
+    pub phases: usize,
+    /// Indices written or compared in the latest phase.
+    pub active: Vec<usize>,
+    pub check: Option<Check>,
+    pub running: bool,
+}
+
+/// Splits `tasks` into `WORKERS` stripes, runs `work` on each task and joins
+/// every worker before returning: the phase barrier.
+async fn phase<R>(name: &'static str, tasks: &[usize], work: impl Fn(usize) -> R + Copy) -> Vec<R> {
+    let size = tasks.len().div_ceil(WORKERS).max(1);
+    let workers = tasks.chunks(size).enumerate().map(|(worker, stripe)| {
+        async move {
+            let mut out = Vec::with_capacity(stripe.len());
+            for &task in stripe {
+                out.push(work(task));
+                gloo_timers::future::TimeoutFuture::new(0).await;
+            }
+            out
+        }
+        .instrument(info_span!("gallery_worker", name, worker))
+    });
+    futures_util::future::join_all(workers).await.into_iter().flatten().collect()
+}
+
+/// Publishes the array after a barrier and pauses for the animation.
+async fn barrier_reached(mut state: Signal<GalleryState>, array_items: Signal<Vec<i32>>, active: Vec<usize>) {
+    let values = read_snapshot();
+    {
+        let mut current = state.write();
+        current.phase += 1;
+        current.active = active;
+        current.values = values;
+        debug!(phase = current.phase, of = current.phases, "barrier reached");
+    }
+    publish_snapshot(array_items);
+    gloo_timers::future::TimeoutFuture::new(PHASE_DELAY_MS).await;
+}
+
+/// Hillis–Steele scan: each stride reads `a[i - d]` for every `i >= d`, then
+/// adds it in a separate phase so no worker reads a value already updated.
+async fn prefix_sum(len: usize, state: Signal<GalleryState>, array_items: Signal<Vec<i32>>) {
+    for d in strides(len) {
+        let targets: Vec<usize> = (d..len).collect();
+        let addends = phase("scan_read", &targets, |i| read_element(i - d)).await;
+        barrier_reached(state, array_items, Vec::new()).await;
+
+        let writes: Vec<usize> = (0..targets.len()).collect();
+        phase("scan_write", &writes, |k| {
+            let addend = addends[k];
+            mutate_element(targets[k], |v| *v = v.wrapping_add(addend));
+        })
+        .await;
+        barrier_reached(state, array_items, targets).await;
+    }
+}
+
+/// `len` phases alternating between even and odd pairs; pairs in one phase
+/// never overlap, so each compare-exchange owns both of its indices.
+async fn odd_even_sort(len: usize, state: Signal<GalleryState>, array_items: Signal<Vec<i32>>) {
+    for round in 0..len {
+        let pairs: Vec<usize> = (round % 2..len.saturating_sub(1)).step_by(2).collect();
+        phase("compare_exchange", &pairs, |i| {
+            let (left, right) = (read_element(i), read_element(i + 1));
+            if left > right {
+                mutate_element(i, |v| *v = right);
+                mutate_element(i + 1, |v| *v = left);
+            }
+        })
+        .await;
+        let active = pairs.iter().flat_map(|i| [*i, *i + 1]).collect();
+        barrier_reached(state, array_items, active).await;
+    }
+}
+
+/// Pairwise sums with a doubling stride; the total ends up in `a[0]`.
+async fn tree_reduction(len: usize, state: Signal<GalleryState>, array_items: Signal<Vec<i32>>) {
+    for s in strides(len) {
+        let targets: Vec<usize> = (0..len).step_by(2 * s).filter(|i| i + s < len).collect();
+        phase("reduce", &targets, |i| {
+            let other = read_element(i + s);
+            mutate_element(i, |v| *v = v.wrapping_add(other));
+        })
+        .await;
+        barrier_reached(state, array_items, targets).await;
+    }
+}
+
+/// Runs `algorithm` over the current array contents and checks the result.
+pub fn run(algorithm: Algorithm, mut state: Signal<GalleryState>, array_items: Signal<Vec<i32>>) {
+    let input = read_snapshot();
+    let len = input.len();
+    state.set(GalleryState {
+        algorithm: Some(algorithm),
+        values: input.clone(),
+        phase: 0,
+        phases: algorithm.phases(len),
+        active: Vec::new(),
+        check: None,
+        running: true,
+    });
+
+    spawn(
+        async move {
+            info!(len, "gallery run started");
+            match algorithm {
+                Algorithm::PrefixSum => prefix_sum(len, state, array_items).await,
+                Algorithm::OddEvenSort => odd_even_sort(len, state, array_items).await,
+                Algorithm::TreeReduction => tree_reduction(len, state, array_items).await,
+            }
+
+            let expected = algorithm.sequential(&input);
+            let actual: Vec<i32> = read_snapshot().into_iter().take(expected.len()).collect();
+            let mismatches: Vec<usize> = (0..expected.len())
+                .filter(|idx| actual.get(*idx) != expected.get(*idx))
+                .collect();
+            if mismatches.is_empty() {
+                info!("parallel result matches the sequential one");
+            } else {
+                warn!(mismatches = mismatches.len(), "parallel result differs from the sequential one");
+            }
+            let mut current = state.write();
+            current.check = Some(Check {
+                expected,
+                actual,
+                mismatches,
+            });
+            current.active.clear();
+            current.running = false;
+        }
+        .instrument(info_span!("gallery", algorithm = algorithm.label())),
+    );
+}
+
+/// Prefix sum, odd-even sort and tree reduction on `CONTIGUOUS_ARRAY`.
+#[component]
+pub fn AlgorithmGallery(array_items: Signal<Vec<i32>>) -> Element {
+    let state = use_signal(GalleryState::default);
+    let mut algorithm = use_signal(|| Algorithm::PrefixSum);
+    let current = state.read();
+
+    rsx! {
+        Panel { id: "gallery-area",
+            SpecCard { label: "GALLERY:", value: "{WORKERS} workers, one barrier per phase",
+                "Classic parallel algorithms on the matrix above. Each phase splits its work into stripes, "
+                "one per worker, and every worker joins before the next phase starts. When the run ends, "
+                "the array is compared with what a single thread computes from the same input."
+            }
+
+            div { class: "trace-toolbar",
+                label {
+                    "Algorithm "
+                    select {
+                        disabled: current.running,
+                        onchange: move |evt| {
+                            let picked = Algorithm::ALL
+                                .into_iter()
+                                .find(|candidate| candidate.label() == evt.value())
+                                .unwrap_or(Algorithm::PrefixSum);
+                            algorithm.set(picked);
+                        },
+                        for candidate in Algorithm::ALL {
+                            option {
+                                value: "{candidate.label()}",
+                                selected: *algorithm.read() == candidate,
+                                "{candidate.label()}"
+                            }
+                        }
+                    }
+                }
+                Button {
+                    variant: Variant::Burst,
+                    loading: current.running,
+                    disabled: array_items.read().is_empty(),
+                    onclick: move |_| {
+                        info!(algorithm = algorithm().label(), "gallery run requested");
+                        run(algorithm(), state, array_items);
+                    },
+                    if current.running { "RUNNING..." } else { "RUN ALGORITHM" }
+                }
+            }
+
+            if let Some(running) = current.algorithm {
+                div { class: "array-header",
+                    span { "{running.label()}" }
+                    span { " | phase {current.phase} of {current.phases}" }
+                }
+                div { class: "array-grid gallery-grid",
+                    for (idx , val) in current.values.iter().enumerate() {
+                        div {
+                            key: "{idx}",
+                            class: "array-cell",
+                            class: if current.active.contains(&idx) { "cell-active" },
+                            div { class: "cell-index", "[{idx}]" }
+                            div { class: "cell-value", "{val}" }
+                        }
+                    }
+                }
+            } else {
+                div { class: "array-empty", "SEED the matrix, pick an algorithm and run it." }
+            }
+
+            if let Some(check) = &current.check {
+                ul { class: "chaos-stats",
+                    li { "sequential: {check.expected:?}" }
+                    li { "parallel:   {check.actual:?}" }
+                    li { class: if check.mismatches.is_empty() { "outcome-hit" } else { "outcome-different" },
+                        if check.mismatches.is_empty() {
+                            "check passed: parallel result matches the sequential one"
+                        } else {
+                            "check failed at {check.mismatches.len()} index(es) {check.mismatches:?}: another demo wrote to the array mid-run"
+                        }
+                    }
+                }
+            }
+
+            div { class: "demo-explanation",
+                h4 { "Phases and barriers" }
+                ul {
+                    li {
+                        strong { "PREFIX SUM: " }
+                        "Hillis–Steele scan. For each stride d, one phase reads a[i - d] and the next adds it to a[i]. "
+                        "Without the barrier between them, a worker could read a value its neighbour already updated."
+                    }
+                    li {
+                        strong { "ODD-EVEN SORT: " }
+                        "n phases alternate between even and odd pairs. Pairs in a phase never share an index, "
+                        "so each compare-exchange owns both cells without a lock."
+                    }
+                    li {
+                        strong { "TREE REDUCTION: " }
+                        "each phase adds a[i + s] into a[i] and doubles s; after log n phases the sum is in a[0]."
+                    }
+                    li {
+                        strong { "TRY IT: " }
+                        "start MATRIX TRANSFORM or CONCURRENT PIPELINE during a run. Each element stays intact, "
+                        "but the algorithm's phases no longer own their indices and the check fails."
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequential_results_wrap_like_the_workers() {
+        let input = [i32::MAX, 1, i32::MIN];
+        assert_eq!(Algorithm::PrefixSum.sequential(&input), vec![i32::MAX, i32::MIN, 0]);
+        assert_eq!(Algorithm::TreeReduction.sequential(&input), vec![0]);
+    }
+
+    #[test]
+    fn a_torn_write_between_the_extremes_does_not_overflow() {
+        assert_eq!(crate::chaos::partial(i32::MIN, i32::MAX), -1);
+        assert_eq!(crate::chaos::partial(i32::MAX, i32::MIN), 0);
+    }
+}
@@ -0,0 +1,213 @@
+//! Syntax highlighting for code blocks in posts.
+//!
+//! A single pass tokenizer, not a parser: it knows Rust well enough for
+//! write-ups about rsmg_core, and `#`-comment languages (TOML, shell, the
+//! demo scripts) well enough to grey out their comments. Anything else is
+//! shown as plain text.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    Type,
+    Macro,
+    Str,
+    Number,
+    Comment,
+}
+
+impl TokenKind {
+    pub fn class(self) -> &'static str {
+        match self {
+            TokenKind::Plain => "tok",
+            TokenKind::Keyword => "tok tok-keyword",
+            TokenKind::Type => "tok tok-type",
+            TokenKind::Macro => "tok tok-macro",
+            TokenKind::Str => "tok tok-str",
+            TokenKind::Number => "tok tok-number",
+            TokenKind::Comment => "tok tok-comment",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Lang {
+    Rust,
+    Hash,
+    Plain,
+}
+
+const RUST_KEYWORDS: [&str; 38] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+];
+
+fn lang(name: &str) -> Lang {
+    match name {
+        "rust" | "rs" => Lang::Rust,
+        "toml" | "sh" | "bash" | "shell" | "scn" => Lang::Hash,
+        _ => Lang::Plain,
+    }
+}
+
+/// Splits `code` into tokens; `language` is the fence's info string.
+pub fn highlight(language: &str, code: &str) -> Vec<Token> {
+    let lang = lang(language);
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut push = |kind: TokenKind, text: &str| match tokens.last_mut() {
+        Some(last) if last.kind == kind && kind == TokenKind::Plain => last.text.push_str(text),
+        _ => tokens.push(Token {
+            kind,
+            text: text.to_string(),
+        }),
+    };
+    if lang == Lang::Plain {
+        push(TokenKind::Plain, code);
+        return tokens;
+    }
+
+    let bytes = code.as_bytes();
+    let mut i = 0;
+    while i < code.len() {
+        let rest = &code[i..];
+        let c = rest.chars().next().expect("i is on a char boundary");
+        let (kind, len) = if (lang == Lang::Rust && rest.starts_with("//")) || (lang == Lang::Hash && c == '#') {
+            (TokenKind::Comment, rest.find('\n').unwrap_or(rest.len()))
+        } else if lang == Lang::Rust && rest.starts_with("/*") {
+            (TokenKind::Comment, rest.find("*/").map_or(rest.len(), |end| end + 2))
+        } else if c == '"' || (lang == Lang::Hash && c == '\'') {
+            (TokenKind::Str, string_len(rest, c))
+        } else if lang == Lang::Rust && c == '\'' {
+            // A char literal closes within a few bytes; a lifetime does not.
+            match rest[1..].find('\'') {
+                Some(end) if end <= 4 && !rest[1..=end].contains(' ') => (TokenKind::Str, end + 2),
+                _ => (TokenKind::Type, 1 + ident_len(&rest[1..])),
+            }
+        } else if c.is_ascii_digit() {
+            let mut end = 0;
+            while let Some(&b) = bytes.get(i + end) {
+                let fraction = b == b'.' && bytes.get(i + end + 1).is_some_and(u8::is_ascii_digit);
+                if !(b.is_ascii_alphanumeric() || b == b'_' || fraction) {
+                    break;
+                }
+                end += 1;
+            }
+            (TokenKind::Number, end)
+        } else if c.is_alphabetic() || c == '_' {
+            let end = ident_len(rest);
+            let word = &rest[..end];
+            if lang != Lang::Rust {
+                (TokenKind::Plain, end)
+            } else if rest[end..].starts_with('!') && !rest[end..].starts_with("!=") {
+                (TokenKind::Macro, end + 1)
+            } else if RUST_KEYWORDS.contains(&word) {
+                (TokenKind::Keyword, end)
+            } else if word.starts_with(char::is_uppercase) {
+                (TokenKind::Type, end)
+            } else {
+                (TokenKind::Plain, end)
+            }
+        } else {
+            (TokenKind::Plain, c.len_utf8())
+        };
+        push(kind, &rest[..len]);
+        i += len;
+    }
+    tokens
+}
+
+fn ident_len(text: &str) -> usize {
+    text.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(text.len())
+}
+
+/// Length of the string literal at the start of `text`, quotes included.
+fn string_len(text: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (at, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if c == quote => return at + 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(language: &str, code: &str) -> Vec<(TokenKind, String)> {
+        highlight(language, code).into_iter().map(|token| (token.kind, token.text)).collect()
+    }
+
+    fn token(kind: TokenKind, text: &str) -> (TokenKind, String) {
+        (kind, text.to_string())
+    }
+
+    #[test]
+    fn tokens_cover_the_source() {
+        let code = "fn main() { let s = \"é \\\" x\"; /* ü */ 'a'; 1.5e3; } // done\n# not a comment";
+        for language in ["rust", "toml", "text", ""] {
+            let joined: String = highlight(language, code).into_iter().map(|token| token.text).collect();
+            assert_eq!(joined, code, "{language}");
+        }
+        let open = "let s = \"never closed";
+        assert_eq!(highlight("rs", open).last().unwrap().kind, TokenKind::Str);
+    }
+
+    #[test]
+    fn classifies_rust() {
+        use TokenKind::*;
+        assert_eq!(
+            kinds("rust", "pub fn f<'a>(x: &'a Vec<u8>) { println!(\"{x:?}\", 'c', 0x1F); } // end"),
+            vec![
+                token(Keyword, "pub"),
+                token(Plain, " "),
+                token(Keyword, "fn"),
+                token(Plain, " f<"),
+                token(Type, "'a"),
+                token(Plain, ">(x: &"),
+                token(Type, "'a"),
+                token(Plain, " "),
+                token(Type, "Vec"),
+                token(Plain, "<u8>) { "),
+                token(Macro, "println!"),
+                token(Plain, "("),
+                token(Str, "\"{x:?}\""),
+                token(Plain, ", "),
+                token(Str, "'c'"),
+                token(Plain, ", "),
+                token(Number, "0x1F"),
+                token(Plain, "); } "),
+                token(Comment, "// end"),
+            ]
+        );
+        assert_eq!(kinds("rust", "a != b")[0], token(Plain, "a != b"));
+    }
+
+    #[test]
+    fn hash_languages_only_mark_comments_strings_and_numbers() {
+        use TokenKind::*;
+        assert_eq!(
+            kinds("toml", "name = 'x' # fn\nfn = 2"),
+            vec![
+                token(Plain, "name = "),
+                token(Str, "'x'"),
+                token(Plain, " "),
+                token(Comment, "# fn"),
+                token(Plain, "\nfn = "),
+                token(Number, "2"),
+            ]
+        );
+        assert_eq!(kinds("python", "# let"), vec![token(Plain, "# let")]);
+    }
+}
@@ -0,0 +1,312 @@
+//! Concurrent bank transfers over a `ContiguousArray` of balances.
+//!
+//! A transfer touches two indices, so it needs two `inspect_element` calls
+//! with a yield in between. The auditor sums every balance between transfer
+//! steps and checks the result against the money that was seeded. The three
+//! modes show where per-element atomicity is enough and where it is not:
+//!
+//! - `TwoLegs`: debit and credit are each one closure. No update is ever
+//!   lost, so the total is right once every transfer completes, but the
+//!   auditor sees money missing while a transfer is between its legs.
+//! - `ReadThenWrite`: each leg reads the balance in one closure and writes
+//!   `balance ± amount` in another. Concurrent transfers on the same account
+//!   overwrite each other and the total drifts permanently.
+//! - `Escrow`: like `TwoLegs`, but the debit moves money into an in-flight
+//!   counter that the auditor adds back. The invariant holds on every audit.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::LazyLock;
+
+use dioxus::prelude::*;
+use rsmg_core::prim::array::ContiguousArray;
+use tracing::{debug, info, info_span, warn, Instrument};
+
+use crate::pop_errors::{drain, PopErrorLog, PopOp};
+use crate::ui::{Button, Panel, SpecCard, Variant};
+
+static LEDGER: LazyLock<ContiguousArray<i32>> = LazyLock::new(ContiguousArray::new);
+static IN_FLIGHT: AtomicI64 = AtomicI64::new(0);
+
+pub const ACCOUNTS: usize = 16;
+pub const OPENING_BALANCE: i32 = 1_000;
+const WORKERS: usize = 4;
+const TRANSFERS_PER_WORKER: usize = 40;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferMode {
+    TwoLegs,
+    ReadThenWrite,
+    Escrow,
+}
+
+impl TransferMode {
+    pub const ALL: [TransferMode; 3] = [TransferMode::TwoLegs, TransferMode::ReadThenWrite, TransferMode::Escrow];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TransferMode::TwoLegs => "two atomic legs",
+            TransferMode::ReadThenWrite => "read, then write",
+            TransferMode::Escrow => "two legs + escrow",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LedgerStats {
+    pub balances: Vec<i32>,
+    pub transfers: usize,
+    pub rejected: usize,
+    pub audits: usize,
+    /// Audits whose total did not match the seeded money.
+    pub violations: usize,
+    pub max_deviation: i64,
+    /// Total minus expected after every transfer finished.
+    pub final_drift: Option<i64>,
+    pub running: usize,
+}
+
+pub fn expected_total() -> i64 {
+    ACCOUNTS as i64 * OPENING_BALANCE as i64
+}
+
+fn balance(idx: usize) -> i32 {
+    let seen = Cell::new(0);
+    LEDGER.inspect_element(idx, |v| seen.set(*v));
+    seen.get()
+}
+
+/// Sums the ledger plus money in escrow. Runs without yielding, so on the
+/// single-threaded web executor no transfer step interleaves with it.
+fn audit(mut stats: Signal<LedgerStats>) -> i64 {
+    let balances: Vec<i32> = (0..LEDGER.len()).map(balance).collect();
+    let total = balances.iter().map(|b| *b as i64).sum::<i64>() + IN_FLIGHT.load(Ordering::Acquire);
+    let deviation = total - expected_total();
+    let mut current = stats.write();
+    current.audits += 1;
+    if deviation != 0 {
+        current.violations += 1;
+        current.max_deviation = current.max_deviation.max(deviation.abs());
+    }
+    current.balances = balances;
+    total
+}
+
+/// Debits `from`, yields, credits `to`. Returns false on insufficient funds.
+async fn transfer(mode: TransferMode, from: usize, to: usize, amount: i32) -> bool {
+    match mode {
+        TransferMode::TwoLegs | TransferMode::Escrow => {
+            let debited = Cell::new(false);
+            LEDGER.inspect_element(from, |v| {
+                if *v >= amount {
+                    *v -= amount;
+                    debited.set(true);
+                }
+            });
+            if !debited.get() {
+                return false;
+            }
+            if mode == TransferMode::Escrow {
+                IN_FLIGHT.fetch_add(amount as i64, Ordering::AcqRel);
+            }
+            gloo_timers::future::TimeoutFuture::new(2).await;
+            LEDGER.inspect_element(to, |v| *v += amount);
+            if mode == TransferMode::Escrow {
+                IN_FLIGHT.fetch_sub(amount as i64, Ordering::AcqRel);
+            }
+            true
+        }
+        TransferMode::ReadThenWrite => {
+            let from_balance = balance(from);
+            if from_balance < amount {
+                return false;
+            }
+            gloo_timers::future::TimeoutFuture::new(1).await;
+            LEDGER.inspect_element(from, |v| *v = from_balance - amount);
+            let to_balance = balance(to);
+            gloo_timers::future::TimeoutFuture::new(1).await;
+            LEDGER.inspect_element(to, |v| *v = to_balance + amount);
+            true
+        }
+    }
+}
+
+/// Closes the old accounts with [`drain`], reopens every account and runs
+/// transfer workers plus the auditor.
+pub fn run(mode: TransferMode, mut stats: Signal<LedgerStats>, pop_errors: Signal<PopErrorLog>) {
+    stats.set(LedgerStats {
+        running: WORKERS,
+        ..LedgerStats::default()
+    });
+    spawn(
+        async move {
+            if let Err(err) = drain(PopOp::LedgerReopen, pop_errors, || LEDGER.pop()).await {
+                warn!(error = %err, "ledger run not started: closing the old accounts failed");
+                stats.write().running = 0;
+                return;
+            }
+            for _ in 0..ACCOUNTS {
+                LEDGER.push(OPENING_BALANCE);
+            }
+            IN_FLIGHT.store(0, Ordering::Release);
+            transfers(mode, stats);
+        }
+        .instrument(info_span!("ledger_reopen")),
+    );
+}
+
+fn transfers(mode: TransferMode, mut stats: Signal<LedgerStats>) {
+    audit(stats);
+
+    for worker in 0..WORKERS {
+        spawn(
+            async move {
+                let mut rng = 0x9E37_79B9_u32.wrapping_mul(worker as u32 + 1);
+                let mut next = move || {
+                    rng ^= rng << 13;
+                    rng ^= rng >> 17;
+                    rng ^= rng << 5;
+                    rng as usize
+                };
+                for _ in 0..TRANSFERS_PER_WORKER {
+                    let from = next() % ACCOUNTS;
+                    let to = (from + 1 + next() % (ACCOUNTS - 1)) % ACCOUNTS;
+                    let amount = 10 + (next() % 200) as i32;
+                    let done = transfer(mode, from, to, amount).await;
+                    debug!(from, to, amount, done, "transfer");
+                    let mut current = stats.write();
+                    if done {
+                        current.transfers += 1;
+                    } else {
+                        current.rejected += 1;
+                    }
+                }
+                stats.write().running -= 1;
+            }
+            .instrument(info_span!("ledger_worker", worker)),
+        );
+    }
+
+    spawn(
+        async move {
+            loop {
+                let total = audit(stats);
+                if total != expected_total() {
+                    debug!(total, expected = expected_total(), "audit mismatch");
+                }
+                if stats.read().running == 0 {
+                    let drift = total - expected_total();
+                    stats.write().final_drift = Some(drift);
+                    if drift == 0 {
+                        info!("ledger conserved after all transfers");
+                    } else {
+                        warn!(drift, "ledger drifted: updates were lost");
+                    }
+                    break;
+                }
+                gloo_timers::future::TimeoutFuture::new(3).await;
+            }
+        }
+        .instrument(info_span!("ledger_auditor")),
+    );
+}
+
+/// Concurrent transfers between account balances with a live auditor.
+#[component]
+pub fn LedgerDemo(pop_errors: Signal<PopErrorLog>) -> Element {
+    let stats = use_signal(LedgerStats::default);
+    let mut mode = use_signal(|| TransferMode::TwoLegs);
+    let current = stats.read();
+    let total: i64 = current.balances.iter().map(|b| *b as i64).sum();
+
+    rsx! {
+        Panel { id: "ledger-area",
+            SpecCard { label: "LEDGER:", value: "{ACCOUNTS} accounts × {OPENING_BALANCE}",
+                "4 workers move money between random pairs of accounts. A transfer is two "
+                strong { "inspect_element" }
+                " calls, one per account, and an auditor checks after every step that the total is still "
+                "{expected_total()}."
+            }
+
+            div { class: "trace-toolbar",
+                label {
+                    "Transfer "
+                    select {
+                        onchange: move |evt| {
+                            let picked = TransferMode::ALL
+                                .into_iter()
+                                .find(|candidate| candidate.label() == evt.value())
+                                .unwrap_or(TransferMode::TwoLegs);
+                            mode.set(picked);
+                        },
+                        for candidate in TransferMode::ALL {
+                            option {
+                                value: "{candidate.label()}",
+                                selected: *mode.read() == candidate,
+                                "{candidate.label()}"
+                            }
+                        }
+                    }
+                }
+                Button {
+                    variant: Variant::Race,
+                    loading: current.running > 0,
+                    onclick: move |_| {
+                        info!(mode = mode().label(), "ledger run started");
+                        run(mode(), stats, pop_errors);
+                    },
+                    if current.running > 0 { "TRANSFERRING..." } else { "RUN TRANSFERS" }
+                }
+            }
+
+            div { class: "array-grid ledger-grid",
+                for (idx , balance) in current.balances.iter().enumerate() {
+                    div { class: "array-cell", key: "{idx}",
+                        div { class: "cell-index", "acct {idx}" }
+                        div { class: "cell-value", "{balance}" }
+                    }
+                }
+            }
+
+            ul { class: "chaos-stats",
+                li { "balances total: {total} (expected {expected_total()})" }
+                li { "transfers: {current.transfers}, rejected for insufficient funds: {current.rejected}" }
+                li {
+                    class: if current.violations > 0 { "outcome-skipped" },
+                    "audits: {current.audits}, invariant broken on {current.violations} (max deviation {current.max_deviation})"
+                }
+                if let Some(drift) = current.final_drift {
+                    li { class: if drift == 0 { "outcome-hit" } else { "outcome-different" },
+                        if drift == 0 {
+                            "final total conserved"
+                        } else {
+                            "final total drifted by {drift}: lost updates"
+                        }
+                    }
+                }
+            }
+
+            div { class: "demo-explanation",
+                h4 { "Where per-element atomicity stops" }
+                ul {
+                    li {
+                        strong { "TWO ATOMIC LEGS: " }
+                        "each leg is a single closure, so no update is lost and the final total is always right. "
+                        "The auditor still catches money missing while a transfer sits between its two calls."
+                    }
+                    li {
+                        strong { "READ, THEN WRITE: " }
+                        "reading in one closure and writing in another lets a concurrent transfer slip in between. "
+                        "Its update is overwritten and the total drifts for good."
+                    }
+                    li {
+                        strong { "ESCROW: " }
+                        "the debit parks the amount in an in-flight counter that audits include. "
+                        "Every audit balances. A ledger needs this kind of bookkeeping, or a lock around both indices, "
+                        "because the primitive only guarantees one element at a time."
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! Conway's Game of Life on a 2D view over `ContiguousArray<u8>`.
+//!
+//! Each generation, `WORKERS` workers update one horizontal stripe each and
+//! yield after every row, so stripes interleave. Generations are separated
+//! by a barrier (all stripe workers join before the next one starts).
+//!
+//! - Double-buffered: workers read the front buffer and write the back one,
+//!   then the buffers swap. The result matches a sequential step exactly.
+//! - In place: workers read and write the same buffer. A cell's neighbours
+//!   may already hold next-generation values, written by the same worker a
+//!   row earlier or by the neighbouring stripe, and patterns smear along
+//!   stripe boundaries.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::LazyLock;
+
+use dioxus::prelude::*;
+use rsmg_core::prim::array::ContiguousArray;
+use tracing::{debug, info_span, warn, Instrument};
+
+use crate::matrix::Matrix2D;
+use crate::pop_errors::{PopErrorLog, PopOp};
+use crate::ui::{Button, Panel, SpecCard, Variant};
+
+static BUFFER_A: LazyLock<ContiguousArray<u8>> = LazyLock::new(ContiguousArray::new);
+static BUFFER_B: LazyLock<ContiguousArray<u8>> = LazyLock::new(ContiguousArray::new);
+/// Which buffer is the front one in double-buffered mode.
+static FRONT_IS_A: AtomicBool = AtomicBool::new(true);
+
+pub const ROWS: usize = 24;
+pub const COLS: usize = 32;
+pub const WORKERS: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LifeMode {
+    DoubleBuffered,
+    InPlace,
+}
+
+impl LifeMode {
+    pub const ALL: [LifeMode; 2] = [LifeMode::DoubleBuffered, LifeMode::InPlace];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LifeMode::DoubleBuffered => "double-buffered",
+            LifeMode::InPlace => "in place",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LifeSeed {
+    Gliders,
+    RPentomino,
+    Random,
+}
+
+impl LifeSeed {
+    pub const ALL: [LifeSeed; 3] = [LifeSeed::Gliders, LifeSeed::RPentomino, LifeSeed::Random];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LifeSeed::Gliders => "gliders",
+            LifeSeed::RPentomino => "r-pentomino",
+            LifeSeed::Random => "random",
+        }
+    }
+
+    fn alive(self, row: usize, col: usize) -> bool {
+        const GLIDER: [(usize, usize); 5] = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+        const R_PENTOMINO: [(usize, usize); 5] = [(0, 1), (0, 2), (1, 0), (1, 1), (2, 1)];
+        match self {
+            LifeSeed::Gliders => [(1, 1), (1, 12), (8, 4), (14, 20), (4, 24)]
+                .iter()
+                .any(|(r, c)| GLIDER.contains(&(row.wrapping_sub(*r), col.wrapping_sub(*c)))),
+            LifeSeed::RPentomino => R_PENTOMINO.contains(&(row.wrapping_sub(10), col.wrapping_sub(14))),
+            LifeSeed::Random => {
+                let mut x = (row * COLS + col) as u32 ^ 0x5bd1_e995;
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                x.is_multiple_of(3)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LifeState {
+    pub cells: Vec<u8>,
+    pub generation: usize,
+    pub population: usize,
+    pub running: bool,
+    pub error: Option<String>,
+}
+
+fn buffers() -> (Matrix2D<u8>, Matrix2D<u8>) {
+    let a = Matrix2D::new(&BUFFER_A, ROWS, COLS);
+    let b = Matrix2D::new(&BUFFER_B, ROWS, COLS);
+    if FRONT_IS_A.load(Ordering::Acquire) {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+fn front() -> Matrix2D<u8> {
+    buffers().0
+}
+
+fn publish(mut state: Signal<LifeState>, generation: usize) {
+    let cells = front().to_vec();
+    let population = cells.iter().filter(|cell| **cell == 1).count();
+    let running = state.read().running;
+    state.set(LifeState {
+        cells,
+        generation,
+        population,
+        running,
+        error: None,
+    });
+}
+
+/// Lays `pattern` out in the front buffer and clears the back one. Returns
+/// false, leaving the grid as it was, if a buffer could not be emptied.
+pub async fn seed(pattern: LifeSeed, mut state: Signal<LifeState>, pop_errors: Signal<PopErrorLog>) -> bool {
+    FRONT_IS_A.store(true, Ordering::Release);
+    let (front, back) = buffers();
+    let filled = match front.fill_with(PopOp::LifeReseed, pop_errors, |row, col| pattern.alive(row, col) as u8).await {
+        Ok(()) => back.fill_with(PopOp::LifeReseed, pop_errors, |_, _| 0).await,
+        Err(err) => Err(err),
+    };
+    if let Err(err) = filled {
+        warn!(error = %err, "life reseed failed");
+        state.write().error = Some(format!("reseed failed: {err}"));
+        return false;
+    }
+    state.write().error = None;
+    publish(state, 0);
+    true
+}
+
+fn next_cell(src: Matrix2D<u8>, row: usize, col: usize) -> u8 {
+    let (r, c) = (row as isize, col as isize);
+    let mut neighbours = 0;
+    for dr in -1..=1 {
+        for dc in -1..=1 {
+            if (dr, dc) != (0, 0) {
+                neighbours += src.get_wrapped(r + dr, c + dc);
+            }
+        }
+    }
+    match (src.get(row, col), neighbours) {
+        (1, 2) | (_, 3) => 1,
+        _ => 0,
+    }
+}
+
+/// Runs one generation with a worker per stripe and waits for all of them.
+pub async fn step(mode: LifeMode, state: Signal<LifeState>) {
+    let (front, back) = buffers();
+    let dst = match mode {
+        LifeMode::DoubleBuffered => back,
+        LifeMode::InPlace => front,
+    };
+    let workers = (0..WORKERS).map(|worker| {
+        async move {
+            for row in front.stripe(worker, WORKERS) {
+                for col in 0..COLS {
+                    dst.set(row, col, next_cell(front, row, col));
+                }
+                gloo_timers::future::TimeoutFuture::new(0).await;
+            }
+        }
+        .instrument(info_span!("life_stripe", worker))
+    });
+    futures_util::future::join_all(workers).await;
+
+    if mode == LifeMode::DoubleBuffered {
+        FRONT_IS_A.fetch_xor(true, Ordering::AcqRel);
+    }
+    let generation = state.read().generation + 1;
+    debug!(generation, mode = mode.label(), "life generation");
+    publish(state, generation);
+}
+
+/// Steps until `state.running` is cleared.
+pub fn run(mode: LifeMode, mut state: Signal<LifeState>) {
+    state.write().running = true;
+    spawn(async move {
+        while state.read().running {
+            step(mode, state).await;
+            gloo_timers::future::TimeoutFuture::new(60).await;
+        }
+    });
+}
+
+pub fn stop(mut state: Signal<LifeState>) {
+    state.write().running = false;
+}
+
+/// Stripe owning `row`, for colouring the grid by worker.
+pub fn stripe_of(row: usize) -> usize {
+    (row / (ROWS / WORKERS)).min(WORKERS - 1)
+}
+
+/// Game of Life with one worker per horizontal stripe.
+#[component]
+pub fn LifeDemo(pop_errors: Signal<PopErrorLog>) -> Element {
+    let state = use_signal(LifeState::default);
+    let mut mode = use_signal(|| LifeMode::DoubleBuffered);
+    let mut pattern = use_signal(|| LifeSeed::Gliders);
+    let current = state.read();
+    let running = current.running;
+
+    rsx! {
+        Panel { id: "life-area",
+            SpecCard { label: "MATRIX:", value: "Matrix2D<u8> {ROWS}×{COLS} over ContiguousArray",
+                "Cell "
+                strong { "(row, col)" }
+                " lives at index "
+                strong { "row * cols + col" }
+                ". Each generation, {WORKERS} workers update one horizontal stripe each and wait at a barrier. "
+                "Double-buffered runs read one array and write the other; in-place runs read and write the same array, "
+                "so a cell can see neighbours that already moved on to the next generation."
+            }
+
+            div { class: "trace-toolbar",
+                label {
+                    "Mode "
+                    select {
+                        disabled: running,
+                        onchange: move |evt| {
+                            let picked = LifeMode::ALL
+                                .into_iter()
+                                .find(|candidate| candidate.label() == evt.value())
+                                .unwrap_or(LifeMode::DoubleBuffered);
+                            mode.set(picked);
+                        },
+                        for candidate in LifeMode::ALL {
+                            option {
+                                value: "{candidate.label()}",
+                                selected: *mode.read() == candidate,
+                                "{candidate.label()}"
+                            }
+                        }
+                    }
+                }
+                label {
+                    "Seed "
+                    select {
+                        disabled: running,
+                        onchange: move |evt| {
+                            let picked = LifeSeed::ALL
+                                .into_iter()
+                                .find(|candidate| candidate.label() == evt.value())
+                                .unwrap_or(LifeSeed::Gliders);
+                            pattern.set(picked);
+                            spawn(async move {
+                                seed(picked, state, pop_errors).await;
+                            });
+                        },
+                        for candidate in LifeSeed::ALL {
+                            option {
+                                value: "{candidate.label()}",
+                                selected: *pattern.read() == candidate,
+                                "{candidate.label()}"
+                            }
+                        }
+                    }
+                }
+                Button {
+                    variant: Variant::Seed,
+                    disabled: running,
+                    onclick: move |_| {
+                        spawn(async move {
+                            seed(pattern(), state, pop_errors).await;
+                        });
+                    },
+                    "RESEED"
+                }
+                Button {
+                    variant: Variant::Secondary,
+                    disabled: running || current.cells.is_empty(),
+                    onclick: move |_| {
+                        spawn(async move { step(mode(), state).await });
+                    },
+                    "STEP"
+                }
+                Button {
+                    variant: if running { Variant::Reset } else { Variant::Burst },
+                    onclick: move |_| {
+                        if running {
+                            stop(state);
+                        } else {
+                            spawn(async move {
+                                if state.read().cells.is_empty() && !seed(pattern(), state, pop_errors).await {
+                                    return;
+                                }
+                                run(mode(), state);
+                            });
+                        }
+                    },
+                    if running { "STOP" } else { "RUN" }
+                }
+            }
+
+            div { class: "array-header",
+                span { "Generation {current.generation}" }
+                span { " | population {current.population} | {mode.read().label()}" }
+            }
+            div {
+                class: "life-grid",
+                style: "grid-template-columns: repeat({COLS}, 1fr);",
+                for (idx , cell) in current.cells.iter().enumerate() {
+                    div {
+                        key: "{idx}",
+                        class: "life-cell stripe-{stripe_of(idx / COLS)}",
+                        class: if *cell == 1 { "alive" },
+                    }
+                }
+            }
+            if let Some(err) = &current.error {
+                div { class: "array-empty outcome-different", "{err}" }
+            } else if current.cells.is_empty() {
+                div { class: "array-empty", "Pick a seed or press RUN to start." }
+            }
+        }
+    }
+}
@@ -1,743 +1,538 @@
 use dioxus::prelude::*;
 use rsmg_core::prim::stack::{LinkedStack, LinkedStackNode};
 use rsmg_core::prim::array::ContiguousArray;
-use std::sync::LazyLock;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use tracing::{debug, info, trace, warn};
+
+mod blog;
+mod boundary;
+mod browser;
+mod chaos;
+mod console;
+mod demos;
+#[cfg(not(target_arch = "wasm32"))]
+mod discovery;
+mod filters;
+mod gallery;
+mod highlight;
+mod ledger;
+mod life;
+mod markdown;
+mod matrix;
+mod persist;
+mod pipeline;
+mod pop_errors;
+mod relocation;
+mod resume;
+mod routes;
+mod scenario;
+mod script;
+mod seo;
+mod site;
+mod snapshot;
+mod telemetry;
+mod theme;
+mod ui;
+
+use chaos::{CellFault, FaultMode};
+use pipeline::{PipelineConfig, StageSpec};
+use pop_errors::{pop_with_retry, PopErrorLog, PopOp, PopResult, RetryPolicy};
+use routes::Route;
+use scenario::ChaosSettings;
 
 // LinkedStack singleton
 static MAGIC_DATA: LazyLock<LinkedStack<i32>> = LazyLock::new(LinkedStack::new);
+/// What `MAGIC_DATA` holds, bottom first. `LinkedStack` only shows its top,
+/// so `stack_push` and `stack_pop` keep this copy for scenarios to read.
+static STACK_MIRROR: Mutex<Vec<i32>> = Mutex::new(Vec::new());
+/// Bumped by every write to either singleton, so the autosave can tell
+/// whether there is anything new without reading them.
+static REVISION: AtomicU64 = AtomicU64::new(0);
 
 // ContiguousArray singleton - the new hotness
 static CONTIGUOUS_ARRAY: LazyLock<ContiguousArray<i32>> = LazyLock::new(ContiguousArray::new);
 
 const FAVICON: Asset = asset!("/assets/favicon.ico");
 const MAIN_CSS: Asset = asset!("/assets/main.css");
-const HEADER_SVG: Asset = asset!("/assets/header.svg");
+
+const RACE_LOG_CAPACITY: usize = 12;
 
 fn main() {
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(dir) = discovery::site_files_dir() {
+        match discovery::write(&dir) {
+            Ok(files) => files.iter().for_each(|file| println!("wrote {}", file.display())),
+            Err(err) => {
+                eprintln!("site-files: {err}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    telemetry::init();
+    // `dx build --ssg` runs the server build once to pre-render every page of
+    // `routes::static_routes` into `public/`, next to the wasm bundle. The
+    // browser build hydrates that HTML instead of rendering from scratch.
+    #[cfg(feature = "server")]
+    dioxus::serve(|| async { Ok(server_router()) });
+    #[cfg(not(feature = "server"))]
     dioxus::launch(App);
 }
 
-#[component]
-fn App() -> Element {
-    let mut stack_items = use_signal(|| vec![]);
-    let mut array_items = use_signal(|| vec![]);
-    let mut race_log = use_signal(|| vec![]);
-
-    let handle_pop = move |_| {
-        spawn(async move {
-            if let Ok(Some(_)) = MAGIC_DATA.pop() {
-                let new_len = stack_items.read().len().saturating_sub(1);
-                
-                if new_len == 0 {
-                    stack_items.set(vec![]);
-                } else {
-                    stack_items.set(vec![new_len as i32; new_len]);
-                }
-            }
-        });
-    };
+/// The app under `base_path`, as `dx serve` proxies it, and at the root,
+/// where the `--ssg` pre-renderer asks for `/api/static_routes` and every
+/// route. Pages are written to `routes::page_dir`, without the base path,
+/// which is where GitHub Pages serves them from.
+#[cfg(feature = "server")]
+fn server_router() -> dioxus::server::axum::Router {
+    use dioxus::server::axum::Router;
+    use dioxus::server::IncrementalRendererConfig;
+
+    let public = public_dir();
+    let pages = IncrementalRendererConfig::new()
+        .static_dir(&public)
+        .map_path(move |route| public.join(routes::page_dir(route)))
+        .clear_cache(false);
+    let cfg = ServeConfig::builder().incremental(pages);
+    let app = Router::new().serve_dioxus_application(cfg, App);
+    Router::new()
+        .nest_service(scenario::BASE_PATH.trim_end_matches('/'), app.clone())
+        .fallback_service(app)
+}
 
-    let array_snapshot = array_items.read().clone();
+/// `public/` next to the server binary, where `dx build` puts the wasm
+/// bundle and `--ssg` writes the pre-rendered pages.
+#[cfg(feature = "server")]
+fn public_dir() -> std::path::PathBuf {
+    let exe = std::env::current_exe()
+        .unwrap_or_else(|err| panic!("cannot locate the server binary, so there is no public/ to render into: {err}"));
+    match exe.parent() {
+        Some(dir) => dir.join("public"),
+        None => panic!("the server binary {} has no parent directory to hold public/", exe.display()),
+    }
+}
 
+#[component]
+fn App() -> Element {
     rsx! {
         document::Link { rel: "icon", href: FAVICON }
+        document::Style { "{*theme::STYLESHEET}" }
+        document::Script { "{theme::boot_script()}" }
         document::Link { rel: "stylesheet", href: MAIN_CSS }
+        if let Ok(site) = &*site::SITE {
+            document::Link {
+                rel: "alternate",
+                r#type: "application/atom+xml",
+                title: "{site.title}",
+                href: site.absolute(&format!("/{}", site::FEED_FILE)),
+            }
+        }
 
-        Hero {}
-
-        main { class: "container",
-
-
-            // ═══════════════════════════════════════════════════════
-            // CONTIGUOUSARRAY DEMO - True Concurrency First
-            // ═══════════════════════════════════════════════════════
-            div { id: "array-area",
-                div { class: "engine-spec",
-                    div { class: "spec-line",
-                        span { class: "spec-label", "ENGINE:" }
-                        span { class: "spec-value", "rsmg_core::prim::array::ContiguousArray" }
-                        a {
-                            class: "small-link-chip",
-                            href: "https://crates.io/crates/rsmg_core",
-                            target: "_blank",
-                            "crates.io"
-                        }
-                    }
-                    p { class: "spec-description",
-                        "A "
-                        strong { "true concurrency-first primitive" }
-                        " that allows "
-                        strong { "simultaneous index-based access" }
-                        " and "
-                        strong { "element-level mutations" }
-                        " across all indices. Multiple workers can operate on different indices concurrently without blocking or corruption."
-                    }
-                }
+        Router::<Route> {}
+    }
+}
 
-                div { class: "controls",
-                    // CONCURRENT MATRIX TRANSFORM - The star of the show
-                    button {
-                        class: "btn btn-burst",
-                        onclick: move |_| {
-                            let len = CONTIGUOUS_ARRAY.len();
-                            if len == 0 {
-                                let mut log = race_log.read().clone();
-                                log.push("SEED first to create a matrix!".to_string());
-                                if log.len() > 12 {
-                                    log.remove(0);
-                                }
-                                race_log.set(log);
-                                return;
-                            }
-
-                            // Launch 4 workers (reduced from 8 for WASM memory)
-                            // Each worker performs different transformations on their section
-
-                            let mut log = race_log.read().clone();
-                            log.push(format!("Launching 4 concurrent workers on {} indices...", len));
-                            if log.len() > 12 {
-                                log.remove(0);
-                            }
-                            race_log.set(log);
-                            let workers = 4; // Reduced from 8
-                            for worker_id in 0..workers {
-                                spawn(async move {
-                                    let section_size = len / workers;
-                                    let start_idx = worker_id * section_size;
-                                    let end_idx = if worker_id == workers - 1 {
-                                        len
-                                    } else {
-                                        (worker_id + 1) * section_size
-                                    };
-                                    // Reduced iterations from 50 to 15 for WASM
-                                    for iteration in 0..15 {
-                                        for idx in start_idx..end_idx {
-                                            CONTIGUOUS_ARRAY
-                                                .inspect_element(
-                                                    idx,
-                                                    |val| {
-                                                        match worker_id % 4 {
-                                                            0 => *val += 1,
-                                                            1 => *val = (*val + 7) % 100,
-                                                            2 => if iteration % 2 == 0 { *val += 2 } else { *val -= 1 }
-                                                            _ => *val = (*val + idx as i32) % 100,
-                                                        }
-                                                    },
-                                                );
-                                        }
-                                        // Update every 3 iterations instead of 5
-                                        if iteration % 3 == 0 {
-                                            let mut snapshot = vec![];
-                                            for i in 0..CONTIGUOUS_ARRAY.len() {
-                                                let cell = std::cell::Cell::new(0);
-                                                CONTIGUOUS_ARRAY.inspect_element(i, |v| cell.set(*v));
-                                                snapshot.push(cell.get());
-                                            }
-                                            array_items.set(snapshot);
-                                            gloo_timers::future::TimeoutFuture::new(10).await;
-                                        } else {
-                                            gloo_timers::future::TimeoutFuture::new(5).await;
-                                        }
-                                    }
-                                    let mut snapshot = vec![];
-                                    for i in 0..CONTIGUOUS_ARRAY.len() {
-                                        let cell = std::cell::Cell::new(0);
-                                        CONTIGUOUS_ARRAY.inspect_element(i, |v| cell.set(*v));
-                                        snapshot.push(cell.get());
-                                    }
-                                    array_items.set(snapshot);
-                                    let mut log = race_log.read().clone();
-                                    log.push(
-                                        format!(
-                                            "Worker {} completed {} transformations",
-                                            worker_id,
-                                            section_size,
-                                        ),
-                                    );
-                                    if log.len() > 12 {
-                                        log.remove(0);
-                                    }
-                                    race_log.set(log);
-                                });
-                            }
-                        },
-                        "MATRIX TRANSFORM (4 workers)"
-                    }
-
-                    // CONCURRENT PIPELINE - Producers + Transformers + Consumers
-                    button {
-                        class: "btn btn-race",
-                        onclick: move |_| {
-                            let mut log = race_log.read().clone();
-                            log.push(
-                                "Starting concurrent pipeline: PRODUCER → TRANSFORMER → CONSUMER"
-                                    .to_string(),
-                            );
-                            if log.len() > 12 {
-                                log.remove(0);
-                            }
-                            race_log.set(log);
-
-                            // Producer workers: reduced to 2 producers, 10 elements each
-                            for producer_id in 0..2 {
-                                spawn(async move {
-                                    for i in 0..10 {
-                                        let value = (producer_id * 10 + i) as i32;
-                                        CONTIGUOUS_ARRAY.push(value);
-                                        if i % 3 == 0 {
-                                            let mut snapshot = vec![];
-                                            for j in 0..CONTIGUOUS_ARRAY.len() {
-                                                let cell = std::cell::Cell::new(0);
-                                                CONTIGUOUS_ARRAY.inspect_element(j, |v| cell.set(*v));
-                                                snapshot.push(cell.get());
-                                            }
-                                            array_items.set(snapshot);
-                                        }
-                                        gloo_timers::future::TimeoutFuture::new(20).await;
-                                    }
-                                    let mut log = race_log.read().clone();
-
-                                    // Transformer worker with limited iterations
-
-                                    // Consumer worker with limited iterations
-                                    log.push(format!("Producer {} finished (10 elements)", producer_id));
-                                    if log.len() > 12 {
-                                        log.remove(0);
-                                    }
-                                    race_log.set(log);
-                                });
-                            }
-                            spawn(async move {
-                                let mut iterations = 0;
-                                while iterations < 50 {
-                                    let len = CONTIGUOUS_ARRAY.len();
-                                    if len == 0 {
-                                        gloo_timers::future::TimeoutFuture::new(50).await;
-                                        iterations += 1;
-                                        continue;
-                                    }
-                                    for _ in 0..5 {
-                                        if len > 0 {
-                                            let idx = (len / 2) % len;
-                                            CONTIGUOUS_ARRAY
-                                                .inspect_element(
-                                                    idx,
-                                                    |val| {
-                                                        *val = (*val * 3 + 7) % 500;
-                                                    },
-                                                );
-                                        }
-                                        gloo_timers::future::TimeoutFuture::new(25).await;
-                                    }
-                                    let mut snapshot = vec![];
-                                    for i in 0..CONTIGUOUS_ARRAY.len() {
-                                        let cell = std::cell::Cell::new(0);
-                                        CONTIGUOUS_ARRAY.inspect_element(i, |v| cell.set(*v));
-                                        snapshot.push(cell.get());
-                                    }
-                                    array_items.set(snapshot);
-                                    iterations += 1;
-                                }
-                            });
-                            spawn(async move {
-                                let mut consumed = 0;
-                                let mut iterations = 0;
-                                while iterations < 30 {
-                                    if let Ok(Some(_val)) = CONTIGUOUS_ARRAY.pop() {
-                                        consumed += 1;
-                                        let mut snapshot = vec![];
-                                        for i in 0..CONTIGUOUS_ARRAY.len() {
-                                            let cell = std::cell::Cell::new(0);
-                                            CONTIGUOUS_ARRAY.inspect_element(i, |v| cell.set(*v));
-                                            snapshot.push(cell.get());
-                                        }
-                                        array_items.set(snapshot);
-                                        if consumed % 5 == 0 {
-                                            let mut log = race_log.read().clone();
-                                            log.push(format!("Consumer: {} elements consumed", consumed));
-                                            if log.len() > 12 {
-                                                log.remove(0);
-                                            }
-                                            race_log.set(log);
-                                        }
-                                    }
-                                    gloo_timers::future::TimeoutFuture::new(50).await;
-                                    iterations += 1;
-                                }
-                            });
-                        },
-                        "CONCURRENT PIPELINE"
-                    }
-
-                    // CONCURRENT STATISTICS - Calculate stats on different sections simultaneously
-                    button {
-                        class: "btn btn-inc",
-                        onclick: move |_| {
-                            let len = CONTIGUOUS_ARRAY.len();
-                            if len == 0 {
-                                let mut log = race_log.read().clone();
-                                log.push("SEED first!".to_string());
-                                if log.len() > 12 {
-                                    log.remove(0);
-                                }
-                                race_log.set(log);
-                                return;
-                            }
-
-                            let mut log = race_log.read().clone();
-
-                            // Each worker calculates different stats on their section
-
-                            // Calculate statistics for this section
-
-                            // Small delay to show concurrent processing
-
-                            // Report results
-
-                            // Update visualization periodically
-
-                            // Also calculate overall statistics
-
-                            log.push("Calculating statistics on 4 sections concurrently...".to_string());
-                            if log.len() > 12 {
-                                log.remove(0);
-                            }
-                            race_log.set(log);
-                            let sections = 4;
-                            let section_size = len / sections;
-                            for worker_id in 0..sections {
-                                spawn(async move {
-                                    let start_idx = worker_id * section_size;
-                                    let end_idx = if worker_id == sections - 1 {
-                                        len
-                                    } else {
-                                        (worker_id + 1) * section_size
-                                    };
-                                    let mut sum = 0i64;
-                                    let mut count = 0;
-                                    let mut max_val = i32::MIN;
-                                    let mut min_val = i32::MAX;
-                                    for idx in start_idx..end_idx {
-                                        let cell = std::cell::Cell::new(0);
-                                        CONTIGUOUS_ARRAY.inspect_element(idx, |v| cell.set(*v));
-                                        let val = cell.get();
-                                        sum += val as i64;
-                                        count += 1;
-                                        if val > max_val {
-                                            max_val = val;
-                                        }
-                                        if val < min_val {
-                                            min_val = val;
-                                        }
-                                        if idx % 4 == 0 {
-                                            gloo_timers::future::TimeoutFuture::new(5).await;
-                                        }
-                                    }
-                                    let avg = if count > 0 { sum / count as i64 } else { 0 };
-                                    let mut log = race_log.read().clone();
-                                    log.push(
-                                        format!(
-                                            "Section {} [{}..{}]: sum={}, avg={}, min={}, max={}",
-                                            worker_id,
-                                            start_idx,
-                                            end_idx - 1,
-                                            sum,
-                                            avg,
-                                            min_val,
-                                            max_val,
-                                        ),
-                                    );
-                                    if log.len() > 12 {
-                                        log.remove(0);
-                                    }
-                                    race_log.set(log);
-                                    let mut snapshot = vec![];
-                                    for i in 0..CONTIGUOUS_ARRAY.len() {
-                                        let cell = std::cell::Cell::new(0);
-                                        CONTIGUOUS_ARRAY.inspect_element(i, |v| cell.set(*v));
-                                        snapshot.push(cell.get());
-                                    }
-                                    array_items.set(snapshot);
-                                });
-                            }
-                            spawn(async move {
-                                gloo_timers::future::TimeoutFuture::new(100).await;
-                                let mut total_sum = 0i64;
-                                let mut total_count = 0;
-                                let mut global_max = i32::MIN;
-                                let mut global_min = i32::MAX;
-                                for idx in 0..CONTIGUOUS_ARRAY.len() {
-                                    let cell = std::cell::Cell::new(0);
-                                    CONTIGUOUS_ARRAY.inspect_element(idx, |v| cell.set(*v));
-                                    let val = cell.get();
-                                    total_sum += val as i64;
-                                    total_count += 1;
-                                    if val > global_max {
-                                        global_max = val;
-                                    }
-                                    if val < global_min {
-                                        global_min = val;
-                                    }
-                                }
-                                let global_avg = if total_count > 0 {
-                                    total_sum / total_count as i64
-                                } else {
-                                    0
-                                };
-                                let mut log = race_log.read().clone();
-                                log.push(
-                                    format!(
-                                        "GLOBAL: sum={}, avg={}, min={}, max={}",
-                                        total_sum,
-                                        global_avg,
-                                        global_min,
-                                        global_max,
-                                    ),
-                                );
-                                if log.len() > 12 {
-                                    log.remove(0);
-                                }
-                                race_log.set(log);
-                            });
-                        },
-                        "CONCURRENT STATISTICS"
-                    }
-
-                    // SEED - Create initial matrix (reduced to 32 elements)
-                    button {
-                        class: "btn btn-seed",
-                        onclick: move |_| {
-                            spawn(async move {
-                                // Clear first
-                                while let Ok(Some(_)) = CONTIGUOUS_ARRAY.pop() {}
-
-                                // Seed with a smaller pattern (32 instead of 64)
-                                for i in 0..32 {
-                                    CONTIGUOUS_ARRAY.push(i * 5);
-                                }
-
-                                let mut snapshot = vec![];
-                                for i in 0..CONTIGUOUS_ARRAY.len() {
-                                    let cell = std::cell::Cell::new(0);
-                                    CONTIGUOUS_ARRAY.inspect_element(i, |v| cell.set(*v));
-                                    snapshot.push(cell.get());
-                                }
-                                array_items.set(snapshot);
-
-                                let mut log = race_log.read().clone();
-                                log.push("Seeded 32-element matrix: [0, 5, 10, 15, ...]".to_string());
-                                if log.len() > 12 {
-                                    log.remove(0);
-                                }
-                                race_log.set(log);
-                            });
-                        },
-                        "SEED MATRIX (32 elements)"
-                    }
-
-                    // RESET
-                    button {
-                        class: "btn btn-reset",
-                        onclick: move |_| {
-                            spawn(async move {
-                                let mut count = 0;
-                                while let Ok(Some(_val)) = CONTIGUOUS_ARRAY.pop() {
-                                    count += 1;
-                                    if count % 10 == 0 {
-                                        gloo_timers::future::TimeoutFuture::new(10).await;
-                                    }
-                                }
-                                array_items.set(vec![]);
-
-                                let mut log = race_log.read().clone();
-                                log.push(format!("Drained {} elements", count));
-                                if log.len() > 12 {
-                                    log.remove(0);
-                                }
-                                race_log.set(log);
-                            });
-                        },
-                        "RESET"
-                    }
-                }
+/// Appends to the operation log, keeping only the newest entries.
+fn push_log(mut race_log: Signal<Vec<String>>, entry: String) {
+    info!(target: "smg_portfolio::race_log", "{entry}");
+    let mut log = race_log.read().clone();
+    log.push(entry);
+    if log.len() > RACE_LOG_CAPACITY {
+        log.remove(0);
+    }
+    race_log.set(log);
+}
 
-                // Array Visualizer - Shows live concurrent operations
-                div { class: "array-visualizer",
-                    div { class: "array-header",
-                        span { "Matrix Size: {array_snapshot.len()} elements" }
-                        span { " | Click any cell to mutate individually" }
-                    }
-                    div { class: "array-grid",
-                        for idx in 0..array_snapshot.len() {
-                            ArrayCell {
-                                idx,
-                                val: array_snapshot.get(idx).copied().unwrap_or(0),
-                                array_items,
-                                race_log,
-                            }
-                        }
-                    }
-                    if array_snapshot.is_empty() {
-                        div { class: "array-empty", "Matrix is empty. Click 'SEED MATRIX' to begin!" }
-                    }
-                }
+/// Records the spawn inside `span` and hands it back for `instrument`.
+fn spawn_span(span: tracing::Span) -> tracing::Span {
+    span.in_scope(|| info!("worker spawned"));
+    span
+}
 
-                // Activity Log - Shows concurrent operation results in real-time
-                div { class: "race-log",
-                    div { class: "log-header", "Live Operation Log" }
-                    for (i , entry) in race_log.read().iter().enumerate() {
-                        div { class: "log-entry", key: "{i}", "{entry}" }
-                    }
-                    if race_log.read().is_empty() {
-                        div { class: "log-entry log-empty",
-                            "No operations yet. Try the concurrent demos above!"
-                        }
-                    }
-                }
+/// Mutates `CONTIGUOUS_ARRAY[idx]` and traces the call.
+///
+/// Returns the value before and after `f`, or `None` when the closure never
+/// ran because `idx` was past the length seen by `inspect_element`. With
+/// chaos enabled the closure may bail out halfway; see `chaos`.
+fn mutate_element(idx: usize, f: impl Fn(&mut i32)) -> Option<(i32, i32)> {
+    if chaos::is_poisoned(idx) {
+        debug!(idx, "inspect_element skipped: index poisoned by a panic");
+        return None;
+    }
+    let fault = chaos::roll();
+    let Some(Mutation { before, intended, left, panicked }) = mutate_in(&CONTIGUOUS_ARRAY, idx, fault, f) else {
+        debug!(idx, "inspect_element skipped");
+        return None;
+    };
+    snapshot::record_write(idx);
+    REVISION.fetch_add(1, Ordering::Relaxed);
+    if panicked {
+        chaos::record_panic(idx, before, intended, left, CONTIGUOUS_ARRAY.len());
+    } else if fault == Some(FaultMode::EarlyReturn) {
+        chaos::record_early_return(idx, before, intended, left);
+    } else {
+        chaos::record_mutation(idx);
+        debug!(idx, before, after = left, "inspect_element");
+    }
+    Some((before, left))
+}
 
-                // Explanation Box
-                div { class: "demo-explanation",
-                    h4 { "True Concurrency First Design" }
-                    ul {
-                        li {
-                            strong { "MATRIX TRANSFORM: " }
-                            "4 workers hitting different sections of the matrix at once. "
-                            "Each worker runs its own transformation logic "
-                            strong { "simultaneously without ever blocking." }
-                        }
-                        li {
-                            strong { "CONCURRENT PIPELINE: " }
-                            "2 producers pushing, 1 transformer mutating, and 1 consumer popping—"
-                            strong { "all at the same time." }
-                            " It’s a literal demonstration of lock-free producer-consumer concurrency."
-                        }
-                        li {
-                            strong { "CONCURRENT STATISTICS: " }
-                            "4 workers crunching stats (sum, avg, min, max) across different chunks simultaneously. "
-                            "A final worker aggregates the global state, "
-                            "proving the array handles concurrent reads without breaking a sweat."
-                        }
-                        li {
-                            strong { "CLICK CELLS: " }
-                            "Try clicking cells while the workers are running. "
-                            "Your manual mutations won't corrupt a thing because every index is independently accessible."
-                        }
-                    }
+/// One closure run by `mutate_in`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Mutation {
+    before: i32,
+    intended: i32,
+    /// What is left in the element.
+    left: i32,
+    panicked: bool,
+}
+
+/// Runs `f` on `array[idx]` through `inspect_element`, misbehaving as
+/// `fault` says. Touches nothing but `array`; `mutate_element` does the
+/// bookkeeping. `None` when the closure never ran.
+fn mutate_in(
+    array: &ContiguousArray<i32>,
+    idx: usize,
+    fault: Option<FaultMode>,
+    f: impl Fn(&mut i32),
+) -> Option<Mutation> {
+    // The element as `inspect_element` handed it over, set before `f` runs so
+    // a panic inside `f` itself still knows which cell it left locked.
+    let entered = Cell::new(None);
+    // (before, intended, left in the element)
+    let seen = Cell::new(None);
+    let run = || {
+        array.inspect_element(idx, |v| {
+            let before = *v;
+            entered.set(Some(before));
+            let mut intended = before;
+            f(&mut intended);
+            match fault {
+                Some(FaultMode::EarlyReturn) => {
+                    *v = chaos::partial(before, intended);
+                    seen.set(Some((before, intended, *v)));
+                }
+                Some(FaultMode::Panic) => {
+                    *v = chaos::partial(before, intended);
+                    seen.set(Some((before, intended, *v)));
+                    panic!("injected fault inside inspect_element at index {idx}");
+                }
+                _ => {
+                    *v = intended;
+                    seen.set(Some((before, intended, *v)));
                 }
             }
+        })
+    };
 
-            // ═══════════════════════════════════════════════════════
-            // LINKEDSTACK DEMO (existing)
-            // ═══════════════════════════════════════════════════════
-            div { id: "magic-area",
-                div { class: "engine-spec",
-                    div { class: "spec-line",
-                        span { class: "spec-label", "ENGINE:" }
-                        span { class: "spec-value", "rsmg_core::prim::stack::LinkedStack" }
-                        a {
-                            class: "small-link-chip",
-                            href: "https://crates.io/crates/rsmg_core",
-                            target: "_blank",
-                            "crates.io"
-                        }
-                    }
-                    p { class: "spec-description",
-                        "This is a magical counter that grows in size as the counter increments. "
-                        "The operation takes advantage of workers doing atomic ops in the background "
-                        "and the renderer loop takes a snapshot of it."
-                    }
-                    div { class: "bonus-challenge",
-                        "BONUS CHALLENGE: Try to make your browser go \"Aw snap!\""
-                    }
-                }
+    #[cfg(not(target_arch = "wasm32"))]
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(run)).is_err();
+    #[cfg(target_arch = "wasm32")]
+    let panicked = {
+        run();
+        false
+    };
 
-                div { class: "controls",
-                    button {
-                        class: "btn btn-inc",
-                        onclick: move |_| {
-                            spawn(async move {
-                                let current_val = stack_items.read().len() as i32 + 1;
-                                MAGIC_DATA.push(LinkedStackNode::new(current_val));
-                                stack_items.set(vec![current_val; current_val as usize]);
-                            });
-                        },
-                        "PUSH"
-                    }
-
-                    button {
-                        class: "btn btn-burst",
-                        onclick: move |_| {
-                            let workers = 4;
-                            let per_worker = 25;
-                            let start_val = stack_items.read().len() as i32;
-
-                            for w in 0..workers {
-                                spawn(async move {
-                                    for i in 1..=per_worker {
-                                        let current_val = start_val + (w * per_worker) + i;
-                                        MAGIC_DATA.push(LinkedStackNode::new(current_val));
-                                        stack_items.set(vec![current_val; current_val as usize]);
-                                        gloo_timers::future::TimeoutFuture::new(0).await;
-                                    }
-                                });
-                            }
-                        },
-                        "BURST x100"
-                    }
-                    button { class: "btn btn-dec", onclick: handle_pop, "POP" }
-
-                    button {
-                        class: "btn btn-reset",
-                        onclick: move |_| {
-                            spawn(async move {
-                                let mut dropped = 0;
-                                while let Ok(Some(_)) = MAGIC_DATA.pop() {
-                                    dropped += 1;
-                                    if dropped % 50 == 0 {
-                                        stack_items.set(vec![0; (dropped % 10) as usize]);
-                                        gloo_timers::future::TimeoutFuture::new(1).await;
-                                    }
-                                }
-                                stack_items.set(vec![]);
-                            });
-                        },
-                        "DRAIN ALL"
-                    }
-                }
+    let (before, intended, left) = match (seen.get(), entered.get()) {
+        (Some(seen), _) => seen,
+        // `f` panicked before deciding anything; the element is unchanged.
+        (None, Some(before)) if panicked => (before, before, before),
+        _ => return None,
+    };
+    Some(Mutation { before, intended, left, panicked })
+}
 
-                div { class: "stack-visualizer",
-                    for (i , val) in stack_items.read().iter().enumerate() {
-                        div {
-                            class: "stack-node",
-                            key: "{val}-{i}",
-                            onclick: handle_pop,
-                            span { "{val}" }
-                        }
-                    }
-                }
-            } // End of interactive/main window
+/// Reads `CONTIGUOUS_ARRAY[idx]`, or 0 when the index is gone. A poisoned
+/// index is never touched; its last known value is returned instead.
+fn read_element(idx: usize) -> i32 {
+    if let CellFault::Poisoned { left, .. } = chaos::cell_fault(idx) {
+        return left;
+    }
+    let cell = Cell::new(0);
+    CONTIGUOUS_ARRAY.inspect_element(idx, |v| cell.set(*v));
+    trace!(idx, value = cell.get(), "inspect_element read");
+    cell.get()
+}
+
+/// `CONTIGUOUS_ARRAY.push`, refused once a panic has leaked `locker_count`
+/// because it would spin forever on the UI thread.
+fn array_push(value: i32) -> bool {
+    if chaos::array_poisoned() {
+        chaos::record_refused("push");
+        return false;
+    }
+    CONTIGUOUS_ARRAY.push(value);
+    snapshot::record_resize();
+    REVISION.fetch_add(1, Ordering::Relaxed);
+    debug!(value, len = CONTIGUOUS_ARRAY.len(), "push");
+    true
+}
+
+/// `MAGIC_DATA.push`, recorded in `STACK_MIRROR`.
+fn stack_push(value: i32) -> Result<(), Box<dyn std::error::Error>> {
+    MAGIC_DATA.push(LinkedStackNode::new(value))?;
+    STACK_MIRROR.lock().unwrap().push(value);
+    REVISION.fetch_add(1, Ordering::Relaxed);
+    Ok(())
+}
+
+/// `MAGIC_DATA.pop`, recorded in `STACK_MIRROR`.
+fn stack_pop() -> PopResult<i32> {
+    let popped = MAGIC_DATA.pop();
+    if let Ok(Some(_)) = popped {
+        STACK_MIRROR.lock().unwrap().pop();
+        REVISION.fetch_add(1, Ordering::Relaxed);
+    }
+    popped
+}
+
+/// The stack's values, bottom first, without touching `MAGIC_DATA`.
+fn stack_snapshot() -> Vec<i32> {
+    STACK_MIRROR.lock().unwrap().clone()
+}
+
+/// `CONTIGUOUS_ARRAY.pop`, refused for the same reason as `array_push`.
+fn array_pop() -> PopResult<i32> {
+    if chaos::array_poisoned() {
+        chaos::record_refused("pop");
+        return Err("refused: a panicking inspect_element callback leaked locker_count".into());
+    }
+    let popped = CONTIGUOUS_ARRAY.pop();
+    if let Ok(Some(_)) = popped {
+        snapshot::record_resize();
+        REVISION.fetch_add(1, Ordering::Relaxed);
+    }
+    popped
+}
+
+fn read_snapshot() -> Vec<i32> {
+    (0..CONTIGUOUS_ARRAY.len()).map(read_element).collect()
+}
+
+fn publish_snapshot(mut array_items: Signal<Vec<i32>>) {
+    let snapshot = read_snapshot();
+    debug!(len = snapshot.len(), "snapshot published");
+    array_items.set(snapshot);
+}
+
+/// State every page shares. `routes::Layout` creates it once and provides it
+/// as context, so it outlives navigation between pages.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Session {
+    pub env: DemoEnv,
+    pub pipeline_config: Signal<PipelineConfig>,
+    pub pipeline_stages: Signal<Vec<StageSpec>>,
+    /// Whether the state is saved for the next reload.
+    pub remember: Signal<bool>,
+    pub scenario_status: Signal<Option<Result<String, String>>>,
+    /// False in pre-rendered HTML and until the browser has hydrated it;
+    /// `DemoSlot` shows previews until then.
+    pub hydrated: Signal<bool>,
+}
+
+impl Session {
+    fn new() -> Self {
+        Self {
+            env: DemoEnv {
+                array_items: Signal::new(Vec::new()),
+                stack_items: Signal::new(Vec::new()),
+                race_log: Signal::new(Vec::new()),
+                pop_errors: Signal::new(PopErrorLog::default()),
+            },
+            pipeline_config: Signal::new(PipelineConfig::default()),
+            pipeline_stages: Signal::new(pipeline::default_stages()),
+            remember: Signal::new(persist::enabled()),
+            scenario_status: Signal::new(None),
+            hydrated: Signal::new(false),
         }
+    }
 
-        // Footer moved outside interactive window/main container
-        footer {
-            class: "site-footer",
-            style: "
-                margin-top: 3.5rem;
-                padding: 2.2rem 0 0.9rem 0;
-                text-align: center;
-                font-size: 1.04em;
-                background: #f5f7fa;
-                color: #444b5d;
-                border-top: 1px solid #e3eaf2;
-                opacity: 0.93;",
-            div { style: "margin-bottom: 0.4em; font-weight: 600; letter-spacing: 0.05em;",
-                a {
-                    href: "https://achsanalfitra.github.io/portfolio/",
-                    style: "color: var(--accent); text-decoration: none;",
-                    "smg-portfolio"
-                }
-                " © 2026"
+    /// Writes the current state; false if it was not written. A state that
+    /// cannot be captured, such as one over the scenario limits, leaves the
+    /// last save in place; a failing store turns persistence off.
+    fn save(mut self) -> bool {
+        let state = match scenario::capture(self.env, self.pipeline_config, self.pipeline_stages) {
+            Ok(state) => state,
+            Err(err) => {
+                warn!(%err, "demo state not saved");
+                self.scenario_status.set(Some(Err(format!("{err}; the last saved state is kept"))));
+                return false;
             }
-            div { style: "font-size:0.95em;",
-                "by "
-                a {
-                    href: "https://github.com/achsanalfitra",
-                    style: "color: var(--accent); text-decoration: none; font-weight: 500;",
-                    "Alfitra Heydar Achsan"
-                }
-                " · Licensed under "
-                a {
-                    href: "https://creativecommons.org/licenses/by-nc/4.0/",
-                    style: "color: var(--accent); text-decoration: underline dotted;",
-                    "CC BY-NC 4.0"
-                }
+        };
+        if let Err(err) = persist::save(&state) {
+            warn!(%err, "saving the demo state failed");
+            self.remember.set(false);
+            self.scenario_status.set(Some(Err(format!("stopped remembering the state: {err}"))));
+            return false;
+        }
+        true
+    }
+
+    /// Changes whenever `save` would write something different.
+    fn save_key(self) -> (u64, ChaosSettings, PipelineConfig, Vec<StageSpec>, Vec<String>) {
+        (
+            REVISION.load(Ordering::Relaxed),
+            ChaosSettings {
+                mode: chaos::mode(),
+                rate: chaos::rate(),
+                seed: chaos::seed(),
+            },
+            (self.pipeline_config)(),
+            (self.pipeline_stages)(),
+            self.env.race_log.read().clone(),
+        )
+    }
+
+    /// Restores a shared link, or else the saved state, then autosaves while
+    /// `remember` is on, once the state has changed and settled. The
+    /// autosave starts only after the restore, so an empty page never
+    /// overwrites what was saved.
+    async fn run(mut self) {
+        let Session {
+            env,
+            pipeline_config,
+            pipeline_stages,
+            ..
+        } = self;
+        let restored = scenario::restore_from_location(env, pipeline_config, pipeline_stages).or_else(|| {
+            persist::load().map(|saved| {
+                saved
+                    .and_then(|saved| scenario::restore(&saved, env, pipeline_config, pipeline_stages))
+                    .map(|()| "restored the state saved before the reload".to_string())
+            })
+        });
+        if let Some(restored) = restored {
+            if let Ok(message) = &restored {
+                push_log(env.race_log, format!("scenario: {message}"));
+            }
+            self.scenario_status.set(Some(restored));
+        }
+        let mut autosave = persist::Autosave::new();
+        loop {
+            gloo_timers::future::TimeoutFuture::new(persist::AUTOSAVE_POLL_MS).await;
+            if !(self.remember)() {
+                autosave.reset();
+                continue;
+            }
+            if autosave.tick(self.save_key()) {
+                self.save();
+                autosave.done();
             }
         }
     }
 }
 
+/// Which singleton a script statement or console command works on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    Array,
+    Stack,
+}
 
-
-#[component]
-fn ArrayCell(idx: usize, val: i32, array_items: Signal<Vec<i32>>, race_log: Signal<Vec<String>>) -> Element {
-    rsx! {
-        div {
-            class: "array-cell",
-            key: "{idx}",
-            onclick: move |_| {
-                let idx = idx;
-                spawn(async move {
-                    // Direct index mutation while other operations may be running
-                    CONTIGUOUS_ARRAY
-                        .inspect_element(
-                            idx,
-                            |v| {
-                                *v = (*v + 1) % 1000;
-                            },
-                        );
-                    // Update snapshot
-                    let mut snapshot = vec![];
-                    for i in 0..CONTIGUOUS_ARRAY.len() {
-                        let cell = std::cell::Cell::new(0);
-                        CONTIGUOUS_ARRAY.inspect_element(i, |v| cell.set(*v));
-                        snapshot.push(cell.get());
-                    }
-                    let new_val = snapshot.get(idx).copied().unwrap_or(0);
-                    array_items.set(snapshot);
-                    let mut log = race_log.read().clone();
-                    log.push(format!("User clicked index[{}] → {}", idx, new_val));
-                    if log.len() > 12 {
-                        log.remove(0);
-                    }
-                    race_log.set(log);
-                });
-            },
-            div { class: "cell-index", "[{idx}]" }
-            div { class: "cell-value", "{val}" }
+impl Target {
+    pub fn label(self) -> &'static str {
+        match self {
+            Target::Array => "array",
+            Target::Stack => "stack",
         }
     }
 }
 
-#[component]
-pub fn Hero() -> Element {
-    rsx! {
-        header { id: "hero",
-            div { class: "hero-content",
-                h1 { "Alfitra Heydar Achsan" }
-                div { class: "hero-subtitle",
-                    p { "Software Engineer | Sidoarjo, Indonesia" }
-                }
-                div { class: "experience-timeline",
-                    div { class: "exp-item",
-                        span { class: "exp-date", "OCT 2025--PRESENT" }
-                        span { class: "exp-role", "Software Engineer as Independent Contractor" }
-                    }
-                    div { class: "exp-item",
-                        span { class: "exp-date", "JUL--OCT 2025" }
-                        span { class: "exp-role", "Software Engineer @ Feroworks" }
-                    }
-                    div { class: "exp-item",
-                        span { class: "exp-date", "JUN--JUL 2025" }
-                        span { class: "exp-role", "Flutter Developer" }
-                    }
-                }
-                div { id: "links",
-                    a {
-                        href: "https://github.com/achsanalfitra",
-                        class: "link-chip",
-                        target: "_blank",
-                        rel: "noopener",
-                        "GITHUB"
-                    }
-                    a {
-                        href: "https://www.linkedin.com/in/alfitra-achsan-025a3019a/",
-                        class: "link-chip",
-                        target: "_blank",
-                        rel: "noopener",
-                        "LINKEDIN"
-                    }
+/// The signals that mirror the two singletons, for code outside `App`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct DemoEnv {
+    pub array_items: Signal<Vec<i32>>,
+    pub stack_items: Signal<Vec<i32>>,
+    pub race_log: Signal<Vec<String>>,
+    pub pop_errors: Signal<PopErrorLog>,
+}
+
+impl DemoEnv {
+    /// `LinkedStack` has no `len`; the stack view tracks it instead.
+    pub fn len(&self, target: Target) -> usize {
+        match target {
+            Target::Array => CONTIGUOUS_ARRAY.len(),
+            Target::Stack => self.stack_items.read().len(),
+        }
+    }
+
+    pub fn publish(&self, target: Target) {
+        if target == Target::Array {
+            publish_snapshot(self.array_items);
+        }
+    }
+
+    pub fn push(&self, target: Target, value: i32) -> Result<(), String> {
+        match target {
+            Target::Array => {
+                if !array_push(value) {
+                    return Err("push refused".to_string());
                 }
             }
+            Target::Stack => {
+                stack_push(value).map_err(|err| format!("stack push failed ({err})"))?;
+                debug!(value, "stack push");
+                let mut stack_items = self.stack_items;
+                let len = stack_items.read().len() + 1;
+                stack_items.set(vec![len as i32; len]);
+            }
         }
+        Ok(())
+    }
+
+    pub async fn pop(&self, target: Target, op: PopOp, policy: RetryPolicy) -> Result<Option<i32>, String> {
+        let popped = match target {
+            Target::Array => pop_with_retry(op, policy, self.pop_errors, array_pop).await,
+            Target::Stack => pop_with_retry(op, policy, self.pop_errors, stack_pop).await,
+        }
+        .map_err(|err| format!("pop failed after retries ({err})"))?;
+        if popped.is_some() && target == Target::Stack {
+            let mut stack_items = self.stack_items;
+            let len = stack_items.read().len().saturating_sub(1);
+            stack_items.set(vec![len as i32; len]);
+        }
+        Ok(popped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh array per case. One a panic left locked would spin in `drop`,
+    /// so it is leaked instead.
+    fn mutate_fresh(fault: Option<FaultMode>, f: impl Fn(&mut i32)) -> Option<Mutation> {
+        let array = ContiguousArray::new();
+        array.push(7);
+        let mutation = mutate_in(&array, 0, fault, f);
+        if mutation.is_some_and(|mutation| mutation.panicked) {
+            std::mem::forget(array);
+        }
+        mutation
+    }
+
+    #[test]
+    fn panic_inside_the_closure_leaves_the_element_unchanged() {
+        let mutation = mutate_fresh(None, |v| *v = v.checked_add(i32::MAX).expect("overflow"));
+        assert_eq!(mutation, Some(Mutation { before: 7, intended: 7, left: 7, panicked: true }));
+    }
+
+    #[test]
+    fn injected_faults_leave_half_the_change() {
+        let torn = mutate_fresh(Some(FaultMode::EarlyReturn), |v| *v += 10);
+        assert_eq!(torn, Some(Mutation { before: 7, intended: 17, left: 12, panicked: false }));
+        let panicked = mutate_fresh(Some(FaultMode::Panic), |v| *v += 10);
+        assert_eq!(panicked, Some(Mutation { before: 7, intended: 17, left: 12, panicked: true }));
+        let clean = mutate_fresh(None, |v| *v += 10);
+        assert_eq!(clean, Some(Mutation { before: 7, intended: 17, left: 17, panicked: false }));
+    }
+
+    #[test]
+    fn past_the_end_never_runs_the_closure() {
+        let array = ContiguousArray::new();
+        assert_eq!(mutate_in(&array, 0, None, |_| unreachable!()), None);
     }
 }
\ No newline at end of file
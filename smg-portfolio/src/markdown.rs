@@ -0,0 +1,400 @@
+//! Markdown to a tree the post components render.
+//!
+//! pulldown-cmark does the parsing; this module folds its events into
+//! `Block`s and `Inline`s so posts go through Dioxus components instead of
+//! `dangerous_inner_html`. Code blocks are highlighted up front, and a
+//! paragraph that is only a shortcode embeds a live demo:
+//!
+//! ```text
+//! {{ demo contiguous_array }}
+//! {{ demo linked_stack }}
+//! ```
+//!
+//! Raw HTML, tables and footnotes are not supported and fail the post.
+//! Links to other pages are site-relative, `[demo](/demos/linked-stack)`;
+//! [`href`] adds the base path when they are rendered.
+
+use std::iter::Peekable;
+
+use dioxus::prelude::*;
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag};
+
+use crate::demos::EmbeddedDemo;
+use crate::highlight::{self, Token};
+use crate::resume::DemoEmbed;
+use crate::scenario::BASE_PATH;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Block {
+    Heading { level: u8, id: String, text: Vec<Inline> },
+    Paragraph(Vec<Inline>),
+    Code { language: String, tokens: Vec<Token> },
+    List { start: Option<u64>, items: Vec<Vec<Block>> },
+    Quote(Vec<Block>),
+    Rule,
+    Demo(DemoEmbed),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Inline {
+    Text(String),
+    Code(String),
+    Emphasis(Vec<Inline>),
+    Strong(Vec<Inline>),
+    Strikethrough(Vec<Inline>),
+    Link { url: String, text: Vec<Inline> },
+    Image { url: String, alt: String },
+    Break,
+}
+
+pub fn parse(source: &str) -> Result<Vec<Block>, String> {
+    blocks(&mut Parser::new_ext(source, Options::ENABLE_STRIKETHROUGH).peekable())
+}
+
+type Events<'a> = Peekable<Parser<'a>>;
+
+fn is_inline(event: &Event) -> bool {
+    match event {
+        Event::Text(_) | Event::Code(_) | Event::InlineHtml(_) | Event::SoftBreak | Event::HardBreak => true,
+        Event::Start(tag) => matches!(
+            tag,
+            Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link { .. } | Tag::Image { .. }
+        ),
+        _ => false,
+    }
+}
+
+/// Blocks up to, and including, the end of the enclosing container.
+fn blocks(events: &mut Events) -> Result<Vec<Block>, String> {
+    let mut out = Vec::new();
+    while let Some(event) = events.peek() {
+        if is_inline(event) {
+            // Items of a tight list hold their text without a paragraph.
+            out.push(Block::Paragraph(inlines(events, true)?));
+            continue;
+        }
+        let block = match events.next().expect("peeked") {
+            Event::End(_) => return Ok(out),
+            Event::Start(Tag::Paragraph) => {
+                let text = inlines(events, false)?;
+                match shortcode(&text) {
+                    Some(demo) => Block::Demo(demo?),
+                    None => Block::Paragraph(text),
+                }
+            }
+            Event::Start(Tag::Heading { level, .. }) => {
+                let text = inlines(events, false)?;
+                Block::Heading {
+                    level: heading_level(level),
+                    id: anchor(&plain_text(&text)),
+                    text,
+                }
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info.split_whitespace().next().unwrap_or_default().to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                let mut code = String::new();
+                for event in events.by_ref() {
+                    match event {
+                        Event::Text(text) => code.push_str(&text),
+                        Event::End(_) => break,
+                        other => return Err(format!("unexpected {other:?} in a code block")),
+                    }
+                }
+                let code = code.strip_suffix('\n').unwrap_or(&code);
+                Block::Code {
+                    tokens: highlight::highlight(&language, code),
+                    language,
+                }
+            }
+            Event::Start(Tag::List(start)) => {
+                let mut items = Vec::new();
+                loop {
+                    match events.next() {
+                        Some(Event::Start(Tag::Item)) => items.push(blocks(events)?),
+                        Some(Event::End(_)) => break,
+                        other => return Err(format!("unexpected {other:?} in a list")),
+                    }
+                }
+                Block::List { start, items }
+            }
+            Event::Start(Tag::BlockQuote(_)) => Block::Quote(blocks(events)?),
+            Event::Rule => Block::Rule,
+            Event::Html(_) | Event::Start(Tag::HtmlBlock) => return Err("raw HTML is not supported".to_string()),
+            other => return Err(format!("unsupported Markdown: {other:?}")),
+        };
+        out.push(block);
+    }
+    Ok(out)
+}
+
+/// Inlines up to the end of the enclosing tag, which is consumed. A `loose`
+/// run has no tag of its own and stops before the next block instead.
+fn inlines(events: &mut Events, loose: bool) -> Result<Vec<Inline>, String> {
+    let mut out = Vec::new();
+    while let Some(event) = events.peek() {
+        if loose && !is_inline(event) {
+            break;
+        }
+        let inline = match events.next().expect("peeked") {
+            Event::End(_) => return Ok(out),
+            Event::Text(text) => Inline::Text(text.into_string()),
+            Event::Code(code) => Inline::Code(code.into_string()),
+            Event::SoftBreak => Inline::Text(" ".to_string()),
+            Event::HardBreak => Inline::Break,
+            Event::Start(Tag::Emphasis) => Inline::Emphasis(inlines(events, false)?),
+            Event::Start(Tag::Strong) => Inline::Strong(inlines(events, false)?),
+            Event::Start(Tag::Strikethrough) => Inline::Strikethrough(inlines(events, false)?),
+            Event::Start(Tag::Link { dest_url, .. }) => Inline::Link {
+                url: dest_url.into_string(),
+                text: inlines(events, false)?,
+            },
+            Event::Start(Tag::Image { dest_url, .. }) => Inline::Image {
+                url: dest_url.into_string(),
+                alt: plain_text(&inlines(events, false)?),
+            },
+            Event::InlineHtml(_) => return Err("raw HTML is not supported".to_string()),
+            other => return Err(format!("unsupported Markdown: {other:?}")),
+        };
+        out.push(inline);
+    }
+    Ok(out)
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+pub fn plain_text(inlines: &[Inline]) -> String {
+    let mut text = String::new();
+    for inline in inlines {
+        match inline {
+            Inline::Text(part) | Inline::Code(part) => text.push_str(part),
+            Inline::Emphasis(inner) | Inline::Strong(inner) | Inline::Strikethrough(inner) => {
+                text.push_str(&plain_text(inner))
+            }
+            Inline::Link { text: inner, .. } => text.push_str(&plain_text(inner)),
+            Inline::Image { alt, .. } => text.push_str(alt),
+            Inline::Break => text.push(' '),
+        }
+    }
+    text
+}
+
+/// Where a link or image `url` points once served: site-relative paths are
+/// moved under `BASE_PATH`, anything else is left alone.
+pub fn href(url: &str) -> String {
+    match url.strip_prefix('/') {
+        Some(path) if !path.starts_with('/') => format!("{BASE_PATH}{path}"),
+        _ => url.to_string(),
+    }
+}
+
+/// Fragment id for a heading, e.g. "Why not a Mutex?" → `why-not-a-mutex`.
+fn anchor(text: &str) -> String {
+    let mut id = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            id.push(c);
+        } else if !id.is_empty() && !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+    id.trim_end_matches('-').to_string()
+}
+
+/// `Some` when the paragraph is a `{{ ... }}` shortcode.
+fn shortcode(text: &[Inline]) -> Option<Result<DemoEmbed, String>> {
+    if !text.iter().all(|inline| matches!(inline, Inline::Text(_))) {
+        return None;
+    }
+    let text = plain_text(text);
+    let inner = text.trim().strip_prefix("{{")?.strip_suffix("}}")?;
+    let words: Vec<&str> = inner.split_whitespace().collect();
+    Some(match words.as_slice() {
+        ["demo", "contiguous_array"] => Ok(DemoEmbed::ContiguousArray),
+        ["demo", "linked_stack"] => Ok(DemoEmbed::LinkedStack),
+        ["demo", other] => Err(format!("unknown demo `{other}`; use contiguous_array or linked_stack")),
+        _ => Err(format!("unknown shortcode `{{{{{inner}}}}}`")),
+    })
+}
+
+/// Post body from [`parse`].
+#[component]
+pub fn MarkdownBlocks(blocks: Vec<Block>) -> Element {
+    rsx! {
+        for block in blocks {
+            match block {
+                Block::Heading { level, id, text } => match level {
+                    1 => rsx! { h1 { id, MarkdownInlines { inlines: text } } },
+                    2 => rsx! { h2 { id, MarkdownInlines { inlines: text } } },
+                    3 => rsx! { h3 { id, MarkdownInlines { inlines: text } } },
+                    4 => rsx! { h4 { id, MarkdownInlines { inlines: text } } },
+                    5 => rsx! { h5 { id, MarkdownInlines { inlines: text } } },
+                    _ => rsx! { h6 { id, MarkdownInlines { inlines: text } } },
+                },
+                Block::Paragraph(text) => rsx! {
+                    p { MarkdownInlines { inlines: text } }
+                },
+                Block::Code { language, tokens } => rsx! {
+                    pre { class: "post-code", "data-language": "{language}",
+                        code {
+                            for token in tokens {
+                                span { class: token.kind.class(), "{token.text}" }
+                            }
+                        }
+                    }
+                },
+                Block::List { start: Some(start), items } => rsx! {
+                    ol { start: "{start}",
+                        for item in items {
+                            li { MarkdownBlocks { blocks: item } }
+                        }
+                    }
+                },
+                Block::List { start: None, items } => rsx! {
+                    ul {
+                        for item in items {
+                            li { MarkdownBlocks { blocks: item } }
+                        }
+                    }
+                },
+                Block::Quote(inner) => rsx! {
+                    blockquote { MarkdownBlocks { blocks: inner } }
+                },
+                Block::Rule => rsx! {
+                    hr {}
+                },
+                Block::Demo(demo) => rsx! {
+                    div { class: "post-demo", EmbeddedDemo { demo } }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+pub fn MarkdownInlines(inlines: Vec<Inline>) -> Element {
+    rsx! {
+        for inline in inlines {
+            match inline {
+                Inline::Text(text) => rsx! { "{text}" },
+                Inline::Code(code) => rsx! { code { "{code}" } },
+                Inline::Emphasis(inner) => rsx! { em { MarkdownInlines { inlines: inner } } },
+                Inline::Strong(inner) => rsx! { strong { MarkdownInlines { inlines: inner } } },
+                Inline::Strikethrough(inner) => rsx! { s { MarkdownInlines { inlines: inner } } },
+                Inline::Link { url, text } if url.starts_with("http") => rsx! {
+                    a { href: "{url}", target: "_blank", rel: "noopener", MarkdownInlines { inlines: text } }
+                },
+                Inline::Link { url, text } => rsx! {
+                    a { href: href(&url), MarkdownInlines { inlines: text } }
+                },
+                Inline::Image { url, alt } => rsx! { img { src: href(&url), alt: "{alt}" } },
+                Inline::Break => rsx! { br {} },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str) -> Inline {
+        Inline::Text(text.to_string())
+    }
+
+    fn paragraph(content: &str) -> Block {
+        Block::Paragraph(vec![text(content)])
+    }
+
+    #[test]
+    fn tight_and_loose_lists_give_the_same_tree() {
+        let items = vec![vec![paragraph("one")], vec![paragraph("two")]];
+        let list = Block::List { start: None, items };
+        assert_eq!(parse("- one\n- two\n"), Ok(vec![list.clone()]));
+        assert_eq!(parse("- one\n\n- two\n"), Ok(vec![list]));
+
+        let nested = parse("3. one\n   - inner\n4. two\n").unwrap();
+        let inner = Block::List {
+            start: None,
+            items: vec![vec![paragraph("inner")]],
+        };
+        let items = vec![vec![paragraph("one"), inner], vec![paragraph("two")]];
+        assert_eq!(nested, vec![Block::List { start: Some(3), items }]);
+    }
+
+    #[test]
+    fn nests_quotes() {
+        let blocks = parse("> outer\n>\n> > inner\n\nafter\n").unwrap();
+        let quote = Block::Quote(vec![paragraph("outer"), Block::Quote(vec![paragraph("inner")])]);
+        assert_eq!(blocks, vec![quote, paragraph("after")]);
+    }
+
+    #[test]
+    fn site_relative_links_get_the_base_path() {
+        let anchor = "/demos/contiguous-array#relocation-area";
+        assert_eq!(href(anchor), format!("/portfolio{anchor}"));
+        assert_eq!(href("/"), "/portfolio/");
+        for url in ["https://crates.io/crates/rsmg_core", "//example.org/x", "#growing", "other-post"] {
+            assert_eq!(href(url), url);
+        }
+    }
+
+    #[test]
+    fn rejects_raw_html() {
+        for source in ["<div>block</div>\n", "some <b>inline</b> html\n", "- <span>in a list</span>\n"] {
+            assert_eq!(parse(source), Err("raw HTML is not supported".to_string()), "{source}");
+        }
+    }
+
+    #[test]
+    fn embeds_demo_shortcodes() {
+        let blocks = parse("{{ demo contiguous_array }}\n\n{{demo linked_stack}}\n").unwrap();
+        assert_eq!(blocks, vec![Block::Demo(DemoEmbed::ContiguousArray), Block::Demo(DemoEmbed::LinkedStack)]);
+
+        let err = parse("{{ demo hash_map }}\n").unwrap_err();
+        assert!(err.contains("unknown demo `hash_map`"), "{err}");
+        let err = parse("{{ chart sales }}\n").unwrap_err();
+        assert_eq!(err, "unknown shortcode `{{ chart sales }}`");
+
+        // Only a whole plain-text paragraph is a shortcode.
+        let inline = "see {{ demo linked_stack }} below";
+        assert_eq!(parse(inline), Ok(vec![paragraph(inline)]));
+        let emphasised = parse("*{{ demo linked_stack }}*\n").unwrap();
+        assert!(matches!(emphasised.as_slice(), [Block::Paragraph(_)]), "{emphasised:?}");
+    }
+
+    #[test]
+    fn headings_get_anchor_slugs() {
+        assert_eq!(anchor("Why not a Mutex?"), "why-not-a-mutex");
+        assert_eq!(anchor("  --ContiguousArray::push()-- "), "contiguousarray-push");
+        assert_eq!(anchor("Größe 42"), "größe-42");
+        assert_eq!(anchor("?!"), "");
+
+        let blocks = parse("## The `pop` *path*\n").unwrap();
+        let Block::Heading { level, id, .. } = &blocks[0] else {
+            panic!("{blocks:?}");
+        };
+        assert_eq!((*level, id.as_str()), (2, "the-pop-path"));
+    }
+
+    #[test]
+    fn highlights_fenced_code_by_language() {
+        let blocks = parse("```rust title=x\nlet a = 1;\n```\n").unwrap();
+        let Block::Code { language, tokens } = &blocks[0] else {
+            panic!("{blocks:?}");
+        };
+        assert_eq!(language, "rust");
+        assert_eq!(tokens, &highlight::highlight("rust", "let a = 1;"));
+    }
+}
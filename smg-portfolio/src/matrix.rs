@@ -0,0 +1,102 @@
+//! Row/column view over a flat `ContiguousArray`.
+//!
+//! Elements are stored row-major, so `(row, col)` lives at
+//! `row * cols + col`. Every access is still a single `inspect_element`;
+//! the view adds indexing, not atomicity across cells.
+
+use std::cell::Cell;
+use std::error::Error;
+use std::ops::Range;
+
+use dioxus::prelude::*;
+use rsmg_core::prim::array::ContiguousArray;
+
+use crate::pop_errors::{drain, PopErrorLog, PopOp};
+
+pub struct Matrix2D<T: 'static> {
+    array: &'static ContiguousArray<T>,
+    rows: usize,
+    cols: usize,
+}
+
+// Manual impls: the view is copyable whatever `T` is.
+impl<T> Clone for Matrix2D<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Matrix2D<T> {}
+
+impl<T: Copy + Default> Matrix2D<T> {
+    pub const fn new(array: &'static ContiguousArray<T>, rows: usize, cols: usize) -> Self {
+        Self { array, rows, cols }
+    }
+
+    pub fn index(&self, row: usize, col: usize) -> usize {
+        debug_assert!(row < self.rows && col < self.cols);
+        row * self.cols + col
+    }
+
+    /// Reads a cell, or `T::default()` if the backing array is too short.
+    pub fn get(&self, row: usize, col: usize) -> T {
+        let seen = Cell::new(T::default());
+        self.array.inspect_element(self.index(row, col), |v| seen.set(*v));
+        seen.get()
+    }
+
+    /// Reads with toroidal wrap-around, for stencils at the edges.
+    pub fn get_wrapped(&self, row: isize, col: isize) -> T {
+        let row = row.rem_euclid(self.rows as isize) as usize;
+        let col = col.rem_euclid(self.cols as isize) as usize;
+        self.get(row, col)
+    }
+
+    /// Reads with coordinates clamped to the nearest edge, for image kernels.
+    pub fn get_clamped(&self, row: isize, col: isize) -> T {
+        let row = row.clamp(0, self.rows as isize - 1) as usize;
+        let col = col.clamp(0, self.cols as isize - 1) as usize;
+        self.get(row, col)
+    }
+
+    pub fn set(&self, row: usize, col: usize, value: T) {
+        self.array.inspect_element(self.index(row, col), |v| *v = value);
+    }
+
+    /// Empties the backing array and refills it to `rows * cols` from `cell`.
+    /// A `pop` that still fails after `DRAIN_RETRY` is returned before
+    /// anything is pushed, so the cells are never laid out after leftovers.
+    pub async fn fill_with(
+        &self,
+        op: PopOp,
+        errors: Signal<PopErrorLog>,
+        cell: impl Fn(usize, usize) -> T,
+    ) -> Result<(), Box<dyn Error>> {
+        let array = self.array;
+        drain(op, errors, || array.pop()).await?;
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                self.array.push(cell(row, col));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn to_vec(self) -> Vec<T> {
+        (0..self.rows * self.cols)
+            .map(|idx| {
+                let seen = Cell::new(T::default());
+                self.array.inspect_element(idx, |v| seen.set(*v));
+                seen.get()
+            })
+            .collect()
+    }
+
+    /// Rows owned by `worker` when the matrix is cut into horizontal stripes.
+    pub fn stripe(&self, worker: usize, workers: usize) -> Range<usize> {
+        let size = self.rows / workers;
+        let start = worker * size;
+        let end = if worker == workers - 1 { self.rows } else { start + size };
+        start..end
+    }
+}
@@ -0,0 +1,216 @@
+//! Keeps the demo state across reloads.
+//!
+//! The saved state is a `Scenario` in its JSON form. The web build keeps it
+//! in `localStorage`, native builds in `smg-portfolio/state.json` under the
+//! platform data directory. Persistence is opt-in: nothing is written until
+//! the user turns it on, and forgetting deletes the saved copy, which also
+//! turns it off again. Other preferences, such as the theme, are kept the
+//! same way under a `Slot` of their own.
+
+pub use store::{read, remove, write};
+
+use crate::scenario::Scenario;
+
+/// Where a value is kept: a `localStorage` key on the web, a file in the
+/// `smg-portfolio` data directory natively.
+pub struct Slot {
+    pub key: &'static str,
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub file: &'static str,
+}
+
+const STATE: Slot = Slot {
+    key: "smg-portfolio.state",
+    file: "state.json",
+};
+
+/// How often the autosave looks for changes while persistence is on.
+pub const AUTOSAVE_POLL_MS: u32 = 250;
+/// A change is saved once nothing else has changed for this long...
+const AUTOSAVE_QUIET_MS: u32 = 1_000;
+/// ...or this long after it was first seen, if the state never settles.
+const AUTOSAVE_MAX_DELAY_MS: u32 = 10_000;
+
+/// Debounces the autosave. `K` is anything cheap that changes whenever the
+/// saved state would.
+pub struct Autosave<K> {
+    saved: Option<K>,
+    seen: Option<K>,
+    quiet_ms: u32,
+    dirty_ms: u32,
+}
+
+impl<K: PartialEq> Autosave<K> {
+    pub const fn new() -> Self {
+        Self {
+            saved: None,
+            seen: None,
+            quiet_ms: 0,
+            dirty_ms: 0,
+        }
+    }
+
+    /// Called every `AUTOSAVE_POLL_MS`; true when `key` is due to be saved.
+    pub fn tick(&mut self, key: K) -> bool {
+        if self.saved.as_ref() == Some(&key) {
+            self.seen = None;
+            self.dirty_ms = 0;
+            return false;
+        }
+        if self.seen.as_ref() == Some(&key) {
+            self.quiet_ms += AUTOSAVE_POLL_MS;
+        } else {
+            self.seen = Some(key);
+            self.quiet_ms = 0;
+        }
+        self.dirty_ms += AUTOSAVE_POLL_MS;
+        self.quiet_ms >= AUTOSAVE_QUIET_MS || self.dirty_ms >= AUTOSAVE_MAX_DELAY_MS
+    }
+
+    /// The due key was saved, or refused; either way it is not tried again
+    /// until something changes.
+    pub fn done(&mut self) {
+        self.saved = self.seen.take();
+        self.dirty_ms = 0;
+    }
+
+    /// Persistence was turned off; the next change is saved from scratch.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Whether a saved state exists, i.e. persistence is on.
+pub fn enabled() -> bool {
+    matches!(read(&STATE), Ok(Some(_)))
+}
+
+/// The saved state, or `None` when nothing has been saved.
+pub fn load() -> Option<Result<Scenario, String>> {
+    match read(&STATE) {
+        Ok(Some(json)) => Some(Scenario::from_json(&json)),
+        Ok(None) => None,
+        Err(err) => Some(Err(err)),
+    }
+}
+
+pub fn save(scenario: &Scenario) -> Result<(), String> {
+    write(&STATE, &scenario.to_json())
+}
+
+pub fn forget() -> Result<(), String> {
+    remove(&STATE)
+}
+
+#[cfg(target_arch = "wasm32")]
+mod store {
+    use web_sys::Storage;
+
+    use super::Slot;
+
+    fn local_storage() -> Result<Storage, String> {
+        web_sys::window()
+            .ok_or("no window")?
+            .local_storage()
+            .map_err(|err| format!("{err:?}"))?
+            .ok_or_else(|| "localStorage is not available".to_string())
+    }
+
+    pub fn read(slot: &Slot) -> Result<Option<String>, String> {
+        local_storage()?.get_item(slot.key).map_err(|err| format!("{err:?}"))
+    }
+
+    pub fn write(slot: &Slot, value: &str) -> Result<(), String> {
+        local_storage()?.set_item(slot.key, value).map_err(|err| format!("{err:?}"))
+    }
+
+    pub fn remove(slot: &Slot) -> Result<(), String> {
+        local_storage()?.remove_item(slot.key).map_err(|err| format!("{err:?}"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod store {
+    use std::io::ErrorKind;
+    use std::path::PathBuf;
+
+    use super::Slot;
+
+    fn path(slot: &Slot) -> Result<PathBuf, String> {
+        let dir = dirs::data_dir().ok_or("no data directory on this platform")?;
+        Ok(dir.join("smg-portfolio").join(slot.file))
+    }
+
+    pub fn read(slot: &Slot) -> Result<Option<String>, String> {
+        let path = path(slot)?;
+        match std::fs::read_to_string(&path) {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!("{}: {err}", path.display())),
+        }
+    }
+
+    pub fn write(slot: &Slot, value: &str) -> Result<(), String> {
+        let path = path(slot)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|err| format!("{}: {err}", dir.display()))?;
+        }
+        std::fs::write(&path, value).map_err(|err| format!("{}: {err}", path.display()))
+    }
+
+    pub fn remove(slot: &Slot) -> Result<(), String> {
+        let path = path(slot)?;
+        match std::fs::remove_file(&path) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(format!("{}: {err}", path.display())),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ticks with `key` until it is due, returning the milliseconds waited.
+    fn wait(autosave: &mut Autosave<u32>, key: u32) -> u32 {
+        let mut waited = 0;
+        while !autosave.tick(key) {
+            waited += AUTOSAVE_POLL_MS;
+            assert!(waited <= AUTOSAVE_MAX_DELAY_MS, "never saved");
+        }
+        waited
+    }
+
+    #[test]
+    fn saves_once_the_state_settles() {
+        let mut autosave = Autosave::new();
+        assert_eq!(wait(&mut autosave, 1), AUTOSAVE_QUIET_MS);
+        autosave.done();
+        for _ in 0..100 {
+            assert!(!autosave.tick(1), "an unchanged state is saved again");
+        }
+        assert_eq!(wait(&mut autosave, 2), AUTOSAVE_QUIET_MS);
+    }
+
+    #[test]
+    fn a_state_that_keeps_changing_is_still_saved() {
+        let mut autosave = Autosave::new();
+        let mut key = 0;
+        let mut waited = 0;
+        while !autosave.tick(key) {
+            key += 1;
+            waited += AUTOSAVE_POLL_MS;
+        }
+        assert_eq!(waited + AUTOSAVE_POLL_MS, AUTOSAVE_MAX_DELAY_MS);
+    }
+
+    #[test]
+    fn a_refused_state_waits_for_the_next_change() {
+        let mut autosave = Autosave::new();
+        wait(&mut autosave, 5);
+        autosave.done();
+        assert!(!autosave.tick(5));
+        autosave.reset();
+        assert_eq!(wait(&mut autosave, 5), AUTOSAVE_QUIET_MS);
+    }
+}
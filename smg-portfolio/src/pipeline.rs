@@ -0,0 +1,670 @@
+//! User-built pipelines of producer, map, filter and consumer stages.
+//!
+//! Every stage's workers share one queue, a `ContiguousArray` or a
+//! `LinkedStack`. Each item records the last stage that handled it, and a
+//! stage only accepts items that have been through every processing stage
+//! before it, so the stage order matters even though the queue is shared:
+//!
+//! - Producer: pushes new items, and waits while the queue is at capacity.
+//! - Map: transforms an item. On the array this is one `inspect_element` on
+//!   the newest eligible index; the stack has no index access, so the item
+//!   is popped, transformed and pushed back.
+//! - Filter: pops an item and pushes it back only if it passes.
+//! - Consumer: pops an item and drops it.
+//!
+//! A popped item that is not ready for the stage goes back on the queue and
+//! counts as a miss. Both structures hand out the newest item first, so
+//! a busy producer can bury older work; the misses and the depth graph
+//! make that visible.
+
+use std::cell::Cell;
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::LazyLock;
+
+use dioxus::prelude::*;
+use rsmg_core::prim::array::ContiguousArray;
+use rsmg_core::prim::stack::{LinkedStack, LinkedStackNode};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, info_span, warn, Instrument};
+
+use crate::pop_errors::{drain, PopErrorLog, PopOp};
+use crate::ui::{Button, Panel, Size, SpecCard, Variant};
+
+static PIPELINE_ARRAY: LazyLock<ContiguousArray<Item>> = LazyLock::new(ContiguousArray::new);
+static PIPELINE_STACK: LazyLock<LinkedStack<Item>> = LazyLock::new(LinkedStack::new);
+/// `LinkedStack` has no `len`, so the stack's depth is counted here.
+static STACK_DEPTH: AtomicUsize = AtomicUsize::new(0);
+static NEXT_VALUE: AtomicUsize = AtomicUsize::new(0);
+/// Bumped by every `run`; tasks of an older run stop at their next turn.
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+pub const MAX_STAGES: usize = 6;
+pub const MAX_WORKERS: usize = 4;
+pub const MAX_CAPACITY: usize = 256;
+pub const MAX_RATE_MS: u32 = 1_000;
+pub const MAX_DURATION_MS: u32 = 60_000;
+const SAMPLE_MS: u32 = 100;
+/// Depth samples kept for the graph.
+const HISTORY: usize = 150;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageKind {
+    Producer,
+    Map,
+    Filter,
+    Consumer,
+}
+
+impl StageKind {
+    pub const ALL: [StageKind; 4] = [StageKind::Producer, StageKind::Map, StageKind::Filter, StageKind::Consumer];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StageKind::Producer => "producer",
+            StageKind::Map => "map",
+            StageKind::Filter => "filter",
+            StageKind::Consumer => "consumer",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backing {
+    Array,
+    Stack,
+}
+
+impl Backing {
+    pub const ALL: [Backing; 2] = [Backing::Array, Backing::Stack];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Backing::Array => "ContiguousArray",
+            Backing::Stack => "LinkedStack",
+        }
+    }
+
+    pub fn depth(self) -> usize {
+        match self {
+            Backing::Array => PIPELINE_ARRAY.len(),
+            Backing::Stack => STACK_DEPTH.load(Ordering::Acquire),
+        }
+    }
+
+    fn push(self, item: Item) -> Result<(), String> {
+        match self {
+            Backing::Array => PIPELINE_ARRAY.push(item),
+            Backing::Stack => {
+                PIPELINE_STACK.push(LinkedStackNode::new(item)).map_err(|err| err.to_string())?;
+                STACK_DEPTH.fetch_add(1, Ordering::AcqRel);
+            }
+        }
+        Ok(())
+    }
+
+    fn pop(self) -> Result<Option<Item>, String> {
+        let popped = match self {
+            Backing::Array => PIPELINE_ARRAY.pop(),
+            Backing::Stack => PIPELINE_STACK.pop(),
+        }
+        .map_err(|err| err.to_string())?;
+        if popped.is_some() && self == Backing::Stack {
+            STACK_DEPTH.fetch_sub(1, Ordering::AcqRel);
+        }
+        Ok(popped)
+    }
+
+    async fn drain(self, pop_errors: Signal<PopErrorLog>) -> Result<(), Box<dyn Error>> {
+        drain(PopOp::PipelineDrain, pop_errors, || self.pop().map_err(Into::into)).await
+    }
+}
+
+/// One stage of the pipeline as configured in the builder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageSpec {
+    pub kind: StageKind,
+    pub workers: usize,
+    /// Pause between two operations of one worker.
+    pub rate_ms: u32,
+}
+
+impl StageSpec {
+    pub fn new(kind: StageKind, workers: usize, rate_ms: u32) -> Self {
+        Self { kind, workers, rate_ms }
+    }
+}
+
+/// The shape the old hard-wired CONCURRENT PIPELINE had.
+pub fn default_stages() -> Vec<StageSpec> {
+    vec![
+        StageSpec::new(StageKind::Producer, 2, 20),
+        StageSpec::new(StageKind::Map, 1, 25),
+        StageSpec::new(StageKind::Consumer, 1, 50),
+    ]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PipelineConfig {
+    pub backing: Backing,
+    /// Producers wait while the queue holds this many items.
+    pub capacity: usize,
+    pub duration_ms: u32,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            backing: Backing::Array,
+            capacity: 32,
+            duration_ms: 8_000,
+        }
+    }
+}
+
+/// Checks a configuration that did not come from the builder's inputs.
+pub fn validate(config: &PipelineConfig, stages: &[StageSpec]) -> Result<(), String> {
+    if stages.is_empty() || stages.len() > MAX_STAGES {
+        return Err(format!("a pipeline has 1 to {MAX_STAGES} stages, not {}", stages.len()));
+    }
+    if !(1..=MAX_CAPACITY).contains(&config.capacity) {
+        return Err(format!("capacity {} is outside 1..={MAX_CAPACITY}", config.capacity));
+    }
+    if !(1_000..=MAX_DURATION_MS).contains(&config.duration_ms) {
+        return Err(format!("duration {} ms is outside 1000..={MAX_DURATION_MS}", config.duration_ms));
+    }
+    for (stage, spec) in stages.iter().enumerate() {
+        if !(1..=MAX_WORKERS).contains(&spec.workers) || !(1..=MAX_RATE_MS).contains(&spec.rate_ms) {
+            return Err(format!(
+                "stage {stage} needs 1 to {MAX_WORKERS} workers and a pace of 1 to {MAX_RATE_MS} ms"
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Item {
+    value: i32,
+    /// Index of the last stage that handled the item.
+    stage: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StageStats {
+    pub processed: usize,
+    /// Filter only: items that failed the predicate.
+    pub dropped: usize,
+    /// Popped an item that was not ready for this stage, or found none.
+    pub misses: usize,
+    /// Producer only: turns spent waiting on a full queue.
+    pub blocked: usize,
+    pub errors: usize,
+}
+
+impl StageStats {
+    pub fn per_second(&self, elapsed_ms: u32) -> f64 {
+        if elapsed_ms == 0 {
+            0.0
+        } else {
+            self.processed as f64 * 1000.0 / elapsed_ms as f64
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PipelineStats {
+    pub stages: Vec<StageStats>,
+    pub depth: Vec<usize>,
+    pub max_depth: usize,
+    pub elapsed_ms: u32,
+    pub running: bool,
+}
+
+/// The last processing stage before `stage`; items must have passed it.
+fn upstream(stages: &[StageSpec], stage: usize) -> usize {
+    stages[..stage]
+        .iter()
+        .rposition(|spec| spec.kind != StageKind::Producer)
+        .unwrap_or(0)
+}
+
+fn ready(item: Item, floor: usize, stage: usize) -> bool {
+    item.stage >= floor && item.stage < stage
+}
+
+fn map_value(value: i32) -> i32 {
+    (value * 3 + 7) % 1000
+}
+
+fn passes_filter(value: i32) -> bool {
+    value % 3 != 0
+}
+
+enum Outcome {
+    Processed,
+    Dropped,
+    Miss,
+    Blocked,
+}
+
+/// One operation of a worker of stage `stage`.
+fn operate(config: PipelineConfig, stages: &[StageSpec], stage: usize) -> Result<Outcome, String> {
+    let backing = config.backing;
+    let floor = upstream(stages, stage);
+    match stages[stage].kind {
+        StageKind::Producer => {
+            if backing.depth() >= config.capacity {
+                return Ok(Outcome::Blocked);
+            }
+            let value = (NEXT_VALUE.fetch_add(1, Ordering::Relaxed) % 1000) as i32;
+            backing.push(Item { value, stage })?;
+            Ok(Outcome::Processed)
+        }
+        StageKind::Map if backing == Backing::Array => {
+            // Newest first, like a pop would see them, but mutated in place.
+            for idx in (0..PIPELINE_ARRAY.len()).rev() {
+                let mapped = Cell::new(false);
+                PIPELINE_ARRAY.inspect_element(idx, |item| {
+                    if ready(*item, floor, stage) {
+                        item.value = map_value(item.value);
+                        item.stage = stage;
+                        mapped.set(true);
+                    }
+                });
+                if mapped.get() {
+                    return Ok(Outcome::Processed);
+                }
+            }
+            Ok(Outcome::Miss)
+        }
+        kind => {
+            let Some(mut item) = backing.pop()? else {
+                return Ok(Outcome::Miss);
+            };
+            if !ready(item, floor, stage) {
+                backing.push(item)?;
+                return Ok(Outcome::Miss);
+            }
+            item.stage = stage;
+            match kind {
+                StageKind::Map => {
+                    item.value = map_value(item.value);
+                    backing.push(item)?;
+                    Ok(Outcome::Processed)
+                }
+                StageKind::Filter if passes_filter(item.value) => {
+                    backing.push(item)?;
+                    Ok(Outcome::Processed)
+                }
+                StageKind::Filter => Ok(Outcome::Dropped),
+                _ => Ok(Outcome::Processed),
+            }
+        }
+    }
+}
+
+/// Empties both queues and runs `stages` until `config.duration_ms` passes
+/// or `stop` is called. Nothing starts unless both queues [`drain`].
+///
+/// Workers sleep between operations, so a stopped run's workers can wake up
+/// after the next one has started with a different stage list. Every task
+/// carries the generation it was started for and exits once it is stale.
+pub fn run(
+    config: PipelineConfig,
+    stages: Vec<StageSpec>,
+    mut stats: Signal<PipelineStats>,
+    pop_errors: Signal<PopErrorLog>,
+) {
+    let generation = GENERATION.fetch_add(1, Ordering::AcqRel) + 1;
+    stats.set(PipelineStats {
+        stages: vec![StageStats::default(); stages.len()],
+        running: true,
+        ..PipelineStats::default()
+    });
+    spawn(
+        async move {
+            let drained = match Backing::Array.drain(pop_errors).await {
+                Ok(()) => Backing::Stack.drain(pop_errors).await,
+                Err(err) => Err(err),
+            };
+            if !is_current(generation) {
+                return;
+            }
+            if let Err(err) = drained {
+                warn!(%err, "pipeline not started: queue drain failed");
+                stats.write().running = false;
+                return;
+            }
+            STACK_DEPTH.store(0, Ordering::Release);
+            start(config, stages, stats, generation);
+        }
+        .instrument(info_span!("pipeline_drain")),
+    );
+}
+
+/// Whether `generation` is still the latest run.
+fn is_current(generation: usize) -> bool {
+    GENERATION.load(Ordering::Acquire) == generation
+}
+
+fn start(config: PipelineConfig, stages: Vec<StageSpec>, mut stats: Signal<PipelineStats>, generation: usize) {
+    info!(backing = config.backing.label(), stages = stages.len(), "pipeline started");
+
+    for (stage, spec) in stages.iter().enumerate() {
+        for worker in 0..spec.workers {
+            let stages = stages.clone();
+            spawn(
+                async move {
+                    while is_current(generation) && stats.read().running {
+                        let outcome = operate(config, &stages, stage);
+                        {
+                            let mut current = stats.write();
+                            let counts = &mut current.stages[stage];
+                            match outcome {
+                                Ok(Outcome::Processed) => counts.processed += 1,
+                                Ok(Outcome::Dropped) => counts.dropped += 1,
+                                Ok(Outcome::Miss) => counts.misses += 1,
+                                Ok(Outcome::Blocked) => counts.blocked += 1,
+                                Err(err) => {
+                                    warn!(%err, "pipeline operation failed");
+                                    counts.errors += 1;
+                                }
+                            }
+                        }
+                        gloo_timers::future::TimeoutFuture::new(stages[stage].rate_ms).await;
+                    }
+                }
+                .instrument(info_span!("pipeline_stage", stage, kind = spec.kind.label(), worker)),
+            );
+        }
+    }
+
+    spawn(
+        async move {
+            loop {
+                gloo_timers::future::TimeoutFuture::new(SAMPLE_MS).await;
+                if !is_current(generation) {
+                    return;
+                }
+                let depth = config.backing.depth();
+                let mut current = stats.write();
+                if !current.running {
+                    break;
+                }
+                current.elapsed_ms += SAMPLE_MS;
+                current.depth.push(depth);
+                if current.depth.len() > HISTORY {
+                    current.depth.remove(0);
+                }
+                current.max_depth = current.max_depth.max(depth);
+                if current.elapsed_ms >= config.duration_ms {
+                    current.running = false;
+                    debug!(depth, "pipeline duration reached");
+                    break;
+                }
+            }
+            info!(depth = config.backing.depth(), "pipeline stopped");
+        }
+        .instrument(info_span!("pipeline_sampler")),
+    );
+}
+
+pub fn stop(mut stats: Signal<PipelineStats>) {
+    stats.write().running = false;
+}
+
+/// Parses a number input, keeping `current` when the text is not a number.
+fn parse_bounded<T: std::str::FromStr + Ord>(text: &str, current: T, min: T, max: T) -> T {
+    text.trim().parse().map_or(current, |value: T| value.clamp(min, max))
+}
+
+/// Compose producer/map/filter/consumer stages over one shared queue.
+/// `config` and `stages` live in `App` so scenarios can save and restore them.
+#[component]
+pub fn PipelineBuilder(
+    mut config: Signal<PipelineConfig>,
+    mut stages: Signal<Vec<StageSpec>>,
+    pop_errors: Signal<PopErrorLog>,
+) -> Element {
+    let stats = use_signal(PipelineStats::default);
+    let current = stats.read();
+    let settings = config();
+    let running = current.running;
+    let stage_list = stages.read().clone();
+
+    // Depth graph: 300×60, scaled to the capacity or the deepest sample.
+    let scale = settings.capacity.max(current.max_depth).max(1) as f64;
+    let step = 300.0 / (current.depth.len().max(2) - 1) as f64;
+    let points = current
+        .depth
+        .iter()
+        .enumerate()
+        .map(|(i, depth)| format!("{:.1},{:.1}", i as f64 * step, 60.0 - *depth as f64 / scale * 60.0))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let capacity_y = 60.0 - settings.capacity as f64 / scale * 60.0;
+    let at_capacity = current.depth.iter().filter(|depth| **depth >= settings.capacity).count();
+
+    rsx! {
+        Panel { id: "pipeline-area",
+            SpecCard { label: "PIPELINE:", value: "{stage_list.len()} stage(s) sharing one {settings.backing.label()}",
+                "Build a pipeline out of producers, maps, filters and consumers, each with its own worker count and pace. "
+                "Every item remembers the last stage that handled it, and a stage only takes items that came through the stages before it. "
+                "The defaults are the shape of CONCURRENT PIPELINE above."
+            }
+
+            div { class: "trace-toolbar",
+                label {
+                    "Queue "
+                    select {
+                        disabled: running,
+                        onchange: move |evt| {
+                            let picked = Backing::ALL
+                                .into_iter()
+                                .find(|candidate| candidate.label() == evt.value())
+                                .unwrap_or(Backing::Array);
+                            config.write().backing = picked;
+                        },
+                        for candidate in Backing::ALL {
+                            option {
+                                value: "{candidate.label()}",
+                                selected: settings.backing == candidate,
+                                "{candidate.label()}"
+                            }
+                        }
+                    }
+                }
+                label {
+                    "Capacity "
+                    input {
+                        class: "pipeline-number",
+                        r#type: "number",
+                        min: "1",
+                        max: "{MAX_CAPACITY}",
+                        disabled: running,
+                        value: "{settings.capacity}",
+                        onchange: move |evt| {
+                            let capacity = parse_bounded(&evt.value(), settings.capacity, 1, MAX_CAPACITY);
+                            config.write().capacity = capacity;
+                        },
+                    }
+                }
+                label {
+                    "Seconds "
+                    input {
+                        class: "pipeline-number",
+                        r#type: "number",
+                        min: "1",
+                        max: "{MAX_DURATION_MS / 1000}",
+                        disabled: running,
+                        value: "{settings.duration_ms / 1000}",
+                        onchange: move |evt| {
+                            let seconds = parse_bounded(&evt.value(), settings.duration_ms / 1000, 1, MAX_DURATION_MS / 1000);
+                            config.write().duration_ms = seconds * 1000;
+                        },
+                    }
+                }
+                Button {
+                    variant: if running { Variant::Reset } else { Variant::Race },
+                    disabled: !running && stage_list.is_empty(),
+                    onclick: move |_| {
+                        if running {
+                            stop(stats);
+                        } else {
+                            run(config(), stages.read().clone(), stats, pop_errors);
+                        }
+                    },
+                    if running { "STOP" } else { "RUN PIPELINE" }
+                }
+            }
+
+            div { class: "pipeline-stages",
+                for (i , spec) in stage_list.iter().copied().enumerate() {
+                    div { class: "pipeline-stage stage-{spec.kind.label()}", key: "{i}",
+                        span { class: "stage-index", "#{i}" }
+                        select {
+                            disabled: running,
+                            onchange: move |evt| {
+                                if let Some(kind) = StageKind::ALL.into_iter().find(|kind| kind.label() == evt.value()) {
+                                    stages.write()[i].kind = kind;
+                                }
+                            },
+                            for kind in StageKind::ALL {
+                                option {
+                                    value: "{kind.label()}",
+                                    selected: spec.kind == kind,
+                                    "{kind.label()}"
+                                }
+                            }
+                        }
+                        label {
+                            "workers "
+                            input {
+                                class: "pipeline-number",
+                                r#type: "number",
+                                min: "1",
+                                max: "{MAX_WORKERS}",
+                                disabled: running,
+                                value: "{spec.workers}",
+                                onchange: move |evt| {
+                                    let workers = parse_bounded(&evt.value(), spec.workers, 1, MAX_WORKERS);
+                                    stages.write()[i].workers = workers;
+                                },
+                            }
+                        }
+                        label {
+                            "every "
+                            input {
+                                class: "pipeline-number",
+                                r#type: "number",
+                                min: "1",
+                                max: "{MAX_RATE_MS}",
+                                disabled: running,
+                                value: "{spec.rate_ms}",
+                                onchange: move |evt| {
+                                    let rate_ms = parse_bounded(&evt.value(), spec.rate_ms, 1, MAX_RATE_MS);
+                                    stages.write()[i].rate_ms = rate_ms;
+                                },
+                            }
+                            " ms"
+                        }
+                        if let Some(counts) = current.stages.get(i) {
+                            span { class: "stage-metrics",
+                                "{counts.processed} done · {counts.per_second(current.elapsed_ms):.1}/s · {counts.misses} misses"
+                                if spec.kind == StageKind::Producer {
+                                    " · {counts.blocked} blocked"
+                                }
+                                if spec.kind == StageKind::Filter {
+                                    " · {counts.dropped} dropped"
+                                }
+                                if counts.errors > 0 {
+                                    " · {counts.errors} errors"
+                                }
+                            }
+                        }
+                        Button {
+                            variant: Variant::Secondary,
+                            size: Size::Compact,
+                            class: "stage-remove",
+                            disabled: running,
+                            onclick: move |_| {
+                                stages.write().remove(i);
+                            },
+                            "×"
+                        }
+                    }
+                }
+            }
+
+            div { class: "trace-toolbar",
+                for kind in StageKind::ALL {
+                    Button {
+                        variant: Variant::Seed,
+                        disabled: running || stage_list.len() >= MAX_STAGES,
+                        onclick: move |_| stages.write().push(StageSpec::new(kind, 1, 50)),
+                        "+ {kind.label()}"
+                    }
+                }
+                Button {
+                    variant: Variant::Secondary,
+                    disabled: running,
+                    onclick: move |_| stages.set(default_stages()),
+                    "DEFAULTS"
+                }
+            }
+
+            if !current.depth.is_empty() {
+                div { class: "array-header",
+                    span { "queue depth over {current.elapsed_ms / 1000}.{current.elapsed_ms % 1000 / 100} s" }
+                    span { " | now {current.depth.last().copied().unwrap_or(0)}, max {current.max_depth}, capacity {settings.capacity}" }
+                }
+                svg {
+                    class: "pipeline-depth",
+                    view_box: "0 0 300 60",
+                    preserve_aspect_ratio: "none",
+                    line {
+                        class: "depth-capacity",
+                        x1: "0",
+                        x2: "300",
+                        y1: "{capacity_y:.1}",
+                        y2: "{capacity_y:.1}",
+                    }
+                    polyline { class: "depth-line", points: "{points}" }
+                }
+                ul { class: "chaos-stats",
+                    li { class: if at_capacity > 0 { "outcome-skipped" },
+                        "backpressure: queue at capacity in {at_capacity} of {current.depth.len()} samples"
+                    }
+                }
+            }
+
+            div { class: "demo-explanation",
+                h4 { "Reading the metrics" }
+                ul {
+                    li {
+                        strong { "DEPTH: " }
+                        "a line that keeps rising means the stages downstream are slower than the producers; "
+                        "it flattens at the capacity once producers start waiting."
+                    }
+                    li {
+                        strong { "BLOCKED: " }
+                        "producer turns spent waiting on a full queue. That is backpressure doing its job."
+                    }
+                    li {
+                        strong { "MISSES: " }
+                        "turns where a stage found nothing ready. Both queues hand out the newest item first, "
+                        "so a consumer behind a slow map keeps popping items the map has not reached yet."
+                    }
+                    li {
+                        strong { "ARRAY VS STACK: " }
+                        "a map on the array updates its item in place with one inspect_element. "
+                        "The stack can only pop and push, so every map also moves the item to the top."
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Error-aware `pop` for the demo arrays and stacks.
+//!
+//! Both `LinkedStack::pop` and `ContiguousArray::pop` return
+//! `Result<Option<T>, Box<dyn Error>>`. The demos used to match only
+//! `Ok(Some(_))`, so an error looked exactly like an empty structure and
+//! ended drain loops early. Every call site now goes through
+//! [`pop_with_retry`], which counts and logs each failure per operation.
+
+use std::collections::VecDeque;
+use std::error::Error;
+
+use dioxus::prelude::*;
+use tracing::warn;
+
+use crate::ui::{LogView, Panel, SpecCard};
+
+pub type PopResult<T> = Result<Option<T>, Box<dyn Error>>;
+
+const RECENT_CAPACITY: usize = 8;
+
+/// Every place in the UI that pops, from the singletons or a demo's own array.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PopOp {
+    ArrayConsolePop,
+    ArrayScriptPop,
+    ArraySeedDrain,
+    ArrayReset,
+    StackPop,
+    StackDrain,
+    StackScriptPop,
+    BoundaryReseed,
+    BoundaryShrink,
+    RelocationReset,
+    LedgerReopen,
+    LifeReseed,
+    FilterInstall,
+    PipelineDrain,
+}
+
+impl PopOp {
+    pub const ALL: [PopOp; 14] = [
+        PopOp::ArrayConsolePop,
+        PopOp::ArrayScriptPop,
+        PopOp::ArraySeedDrain,
+        PopOp::ArrayReset,
+        PopOp::StackPop,
+        PopOp::StackDrain,
+        PopOp::StackScriptPop,
+        PopOp::BoundaryReseed,
+        PopOp::BoundaryShrink,
+        PopOp::RelocationReset,
+        PopOp::LedgerReopen,
+        PopOp::LifeReseed,
+        PopOp::FilterInstall,
+        PopOp::PipelineDrain,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PopOp::ArrayConsolePop => "array: console pop",
+            PopOp::ArrayScriptPop => "array: script pop",
+            PopOp::ArraySeedDrain => "array: seed drain",
+            PopOp::ArrayReset => "array: reset drain",
+            PopOp::StackPop => "stack: pop",
+            PopOp::StackDrain => "stack: drain all",
+            PopOp::StackScriptPop => "stack: script pop",
+            PopOp::BoundaryReseed => "explorer: reseed drain",
+            PopOp::BoundaryShrink => "explorer: shrinker pop",
+            PopOp::RelocationReset => "detector: reset drain",
+            PopOp::LedgerReopen => "ledger: reopen drain",
+            PopOp::LifeReseed => "life: reseed drain",
+            PopOp::FilterInstall => "filters: install drain",
+            PopOp::PipelineDrain => "pipeline: queue drain",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// How many times a failing `pop` is attempted before the caller gives up.
+/// The delay before attempt `n` is `backoff_ms * (n - 1)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub backoff_ms: u32,
+}
+
+pub const DRAIN_RETRY: RetryPolicy = RetryPolicy {
+    max_attempts: 3,
+    backoff_ms: 5,
+};
+
+pub const CONSUMER_RETRY: RetryPolicy = RetryPolicy {
+    max_attempts: 5,
+    backoff_ms: 20,
+};
+
+/// Single user-initiated pops are reported but never retried.
+pub const NO_RETRY: RetryPolicy = RetryPolicy {
+    max_attempts: 1,
+    backoff_ms: 0,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PopErrorRecord {
+    pub op: PopOp,
+    pub attempt: u32,
+    pub message: String,
+    /// `Debug` rendering of the boxed error, which names its variant.
+    pub variant: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PopOpStats {
+    pub errors: usize,
+    pub retries: usize,
+    pub gave_up: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PopErrorLog {
+    stats: [PopOpStats; PopOp::ALL.len()],
+    recent: VecDeque<PopErrorRecord>,
+}
+
+impl PopErrorLog {
+    pub fn stats(&self, op: PopOp) -> PopOpStats {
+        self.stats[op.index()]
+    }
+
+    pub fn total_errors(&self) -> usize {
+        self.stats.iter().map(|stats| stats.errors).sum()
+    }
+
+    /// Newest first.
+    pub fn recent(&self) -> impl Iterator<Item = &PopErrorRecord> {
+        self.recent.iter().rev()
+    }
+
+    fn record(&mut self, op: PopOp, attempt: u32, err: &dyn Error) {
+        self.stats[op.index()].errors += 1;
+        if self.recent.len() == RECENT_CAPACITY {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(PopErrorRecord {
+            op,
+            attempt,
+            message: err.to_string(),
+            variant: format!("{err:?}"),
+        });
+    }
+}
+
+/// Calls `pop` until it succeeds or `policy` runs out of attempts.
+///
+/// `Ok(None)` is a genuine empty structure and is returned immediately.
+/// Every `Err` is counted in `errors` and logged; the last one is returned
+/// once the policy is exhausted.
+pub async fn pop_with_retry<T>(
+    op: PopOp,
+    policy: RetryPolicy,
+    mut errors: Signal<PopErrorLog>,
+    pop: impl Fn() -> PopResult<T>,
+) -> PopResult<T> {
+    let mut attempt = 1;
+    loop {
+        match pop() {
+            Err(err) => {
+                warn!(op = op.label(), attempt, error = %err, variant = ?err, "pop failed");
+                let mut log = errors.write();
+                log.record(op, attempt, err.as_ref());
+                if attempt >= policy.max_attempts {
+                    log.stats[op.index()].gave_up += 1;
+                    return Err(err);
+                }
+                log.stats[op.index()].retries += 1;
+                drop(log);
+                gloo_timers::future::TimeoutFuture::new(policy.backoff_ms * attempt).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Pops until `pop` reports an empty structure, retrying under `DRAIN_RETRY`.
+///
+/// Demos drain before they reseed and start their workers. A `pop` that
+/// still fails is returned so the caller can stop instead of racing over
+/// leftovers; it is already counted in `errors`, which is where the user
+/// sees why nothing started.
+pub async fn drain<T>(
+    op: PopOp,
+    errors: Signal<PopErrorLog>,
+    pop: impl Fn() -> PopResult<T>,
+) -> Result<(), Box<dyn Error>> {
+    while pop_with_retry(op, DRAIN_RETRY, errors, &pop).await?.is_some() {}
+    Ok(())
+}
+
+/// Per-operation pop failures, with the retry policy each call site uses.
+#[component]
+pub fn ErrorPanel(pop_errors: Signal<PopErrorLog>) -> Element {
+    let log = pop_errors.read();
+    let total = log.total_errors();
+
+    rsx! {
+        Panel { id: "error-area",
+            SpecCard { label: "POP ERRORS:", value: "{total} recorded",
+                "Both primitives return "
+                strong { "Result<Option<T>, _>" }
+                " from pop. "
+                strong { "Ok(None)" }
+                " means empty; an "
+                strong { "Err" }
+                " is counted here and retried: drains try "
+                "{DRAIN_RETRY.max_attempts} times ({DRAIN_RETRY.backoff_ms}ms linear backoff), "
+                "script pops {CONSUMER_RETRY.max_attempts} times ({CONSUMER_RETRY.backoff_ms}ms), "
+                "and a single POP is never retried."
+            }
+
+            table { class: "error-table",
+                thead {
+                    tr {
+                        th { "Operation" }
+                        th { "Errors" }
+                        th { "Retries" }
+                        th { "Gave up" }
+                    }
+                }
+                tbody {
+                    for op in PopOp::ALL {
+                        tr { key: "{op.label()}",
+                            class: if log.stats(op).errors > 0 { "error-row-hot" },
+                            td { "{op.label()}" }
+                            td { "{log.stats(op).errors}" }
+                            td { "{log.stats(op).retries}" }
+                            td { "{log.stats(op).gave_up}" }
+                        }
+                    }
+                }
+            }
+
+            LogView {
+                title: "Recent Pop Errors",
+                empty: total == 0,
+                placeholder: "No pop has failed so far.",
+                for (i , record) in log.recent().enumerate() {
+                    div { class: "log-entry error-entry", key: "{i}",
+                        "[{record.op.label()}] attempt {record.attempt}: {record.message} — {record.variant}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! Watches element addresses while producers grow a `ContiguousArray`.
+//!
+//! Observers sweep the array and record `&mut T as *const T` from inside each
+//! `inspect_element` closure, keyed by a unique tag stored in the element. If
+//! the same tag ever shows up at a different address, the element moved and
+//! the report names the pushes that happened in between.
+//!
+//! `rsmg_core` boxes every element behind its own handle and only the table
+//! of handle pointers is reallocated on growth, so the expected result is
+//! many table reallocations and zero element relocations. The detector
+//! checks that instead of assuming it.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::LazyLock;
+
+use dioxus::prelude::*;
+use rsmg_core::prim::array::ContiguousArray;
+use tracing::{debug, info_span, warn, Instrument};
+
+use crate::pop_errors::{drain, PopErrorLog, PopOp};
+use crate::ui::{Button, LogView, Panel, SpecCard, Variant};
+
+static RELOCATION_ARRAY: LazyLock<ContiguousArray<i32>> = LazyLock::new(ContiguousArray::new);
+static NEXT_TAG: AtomicI32 = AtomicI32::new(1);
+
+/// Mirrors `DEFAULT_CAPACITY` in `rsmg_core::prim::array`; the array doubles
+/// from there. Only used to annotate which pushes reallocated the table.
+const INITIAL_CAPACITY: usize = 8 * size_of::<*mut u8>();
+
+const PRODUCERS: usize = 2;
+const PUSHES_PER_PRODUCER: usize = 160;
+const OBSERVERS: usize = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PushEvent {
+    pub seq: usize,
+    pub producer: usize,
+    pub len_before: usize,
+    /// The predicted capacity was full, so this push reallocated the table.
+    pub grew_table: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Relocation {
+    pub tag: i32,
+    pub idx: usize,
+    pub old_addr: usize,
+    pub new_addr: usize,
+    /// Pushes between the two observations; any of them may be the trigger.
+    pub suspects: Vec<PushEvent>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Sighting {
+    addr: usize,
+    push_seq: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RelocationStats {
+    pub observations: usize,
+    pub relocations: Vec<Relocation>,
+    pub pushes: Vec<PushEvent>,
+    pub running: usize,
+    sightings: HashMap<i32, Sighting>,
+}
+
+impl RelocationStats {
+    pub fn tracked_elements(&self) -> usize {
+        self.sightings.len()
+    }
+
+    pub fn table_growths(&self) -> impl Iterator<Item = &PushEvent> {
+        self.pushes.iter().filter(|push| push.grew_table)
+    }
+
+    fn observe(&mut self, tag: i32, idx: usize, addr: usize) {
+        self.observations += 1;
+        let push_seq = self.pushes.len();
+        let previous = self.sightings.insert(tag, Sighting { addr, push_seq });
+        if let Some(previous) = previous {
+            if previous.addr != addr {
+                let suspects = self.pushes[previous.push_seq..push_seq].to_vec();
+                warn!(tag, idx, old_addr = previous.addr, new_addr = addr, suspects = suspects.len(), "element relocated");
+                self.relocations.push(Relocation {
+                    tag,
+                    idx,
+                    old_addr: previous.addr,
+                    new_addr: addr,
+                    suspects,
+                });
+            }
+        }
+    }
+}
+
+pub fn len() -> usize {
+    RELOCATION_ARRAY.len()
+}
+
+fn predicted_capacity(len: usize) -> usize {
+    let mut capacity = INITIAL_CAPACITY;
+    while capacity < len {
+        capacity *= 2;
+    }
+    capacity
+}
+
+/// Empties the array and starts producers and observers; see [`drain`] for
+/// what happens when the array cannot be emptied.
+pub fn run(mut stats: Signal<RelocationStats>, pop_errors: Signal<PopErrorLog>) {
+    stats.set(RelocationStats {
+        running: PRODUCERS + OBSERVERS,
+        ..RelocationStats::default()
+    });
+    spawn(
+        async move {
+            if let Err(err) = drain(PopOp::RelocationReset, pop_errors, || RELOCATION_ARRAY.pop()).await {
+                warn!(error = %err, "relocation detector not started: reset drain failed");
+                stats.write().running = 0;
+                return;
+            }
+            watch(stats);
+        }
+        .instrument(info_span!("relocation_reset")),
+    );
+}
+
+fn watch(mut stats: Signal<RelocationStats>) {
+    for producer in 0..PRODUCERS {
+        spawn(
+            async move {
+                for i in 0..PUSHES_PER_PRODUCER {
+                    let len_before = RELOCATION_ARRAY.len();
+                    RELOCATION_ARRAY.push(NEXT_TAG.fetch_add(1, Ordering::Relaxed));
+                    let mut current = stats.write();
+                    let seq = current.pushes.len() + 1;
+                    let grew_table = len_before == predicted_capacity(len_before);
+                    if grew_table {
+                        debug!(seq, len_before, "push reallocated the handle table");
+                    }
+                    current.pushes.push(PushEvent {
+                        seq,
+                        producer,
+                        len_before,
+                        grew_table,
+                    });
+                    drop(current);
+                    if i % 8 == 0 {
+                        gloo_timers::future::TimeoutFuture::new(1).await;
+                    }
+                }
+                stats.write().running -= 1;
+            }
+            .instrument(info_span!("relocation_producer", producer)),
+        );
+    }
+
+    for observer in 0..OBSERVERS {
+        spawn(
+            async move {
+                loop {
+                    let producing = stats.read().running > OBSERVERS;
+                    for idx in 0..RELOCATION_ARRAY.len() {
+                        let seen = std::cell::Cell::new(None);
+                        RELOCATION_ARRAY.inspect_element(idx, |v| {
+                            seen.set(Some((*v, v as *const i32 as usize)));
+                        });
+                        if let Some((tag, addr)) = seen.get() {
+                            stats.write().observe(tag, idx, addr);
+                        }
+                        if idx % 16 == 0 {
+                            gloo_timers::future::TimeoutFuture::new(0).await;
+                        }
+                    }
+                    // One last sweep after the producers stop, then exit.
+                    if !producing {
+                        break;
+                    }
+                    gloo_timers::future::TimeoutFuture::new(2).await;
+                }
+                stats.write().running -= 1;
+            }
+            .instrument(info_span!("relocation_observer", observer)),
+        );
+    }
+}
+
+/// Tracks element addresses seen inside `inspect_element` while producers
+/// push the array through several table reallocations.
+#[component]
+pub fn RelocationDetector(pop_errors: Signal<PopErrorLog>) -> Element {
+    let stats = use_signal(RelocationStats::default);
+    let current = stats.read();
+    let growths = current
+        .table_growths()
+        .map(|push| format!("#{} (len {})", push.seq, push.len_before))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    rsx! {
+        Panel { id: "relocation-area",
+            SpecCard { label: "DETECTOR:", value: "element addresses across push growth",
+                "Two producers push tagged elements while two observers record the address of every element "
+                "from inside "
+                strong { "inspect_element" }
+                ". The same tag at a new address is flagged as a relocation, together with the pushes "
+                "that ran between the two sightings."
+            }
+
+            div { class: "trace-toolbar",
+                Button {
+                    variant: Variant::Burst,
+                    loading: current.running > 0,
+                    onclick: move |_| {
+                        info!("relocation detector started");
+                        run(stats, pop_errors);
+                    },
+                    if current.running > 0 { "WATCHING..." } else { "GROW AND WATCH" }
+                }
+            }
+
+            ul { class: "chaos-stats",
+                li { "len: {len()}, pushes: {current.pushes.len()}, tracked elements: {current.tracked_elements()}" }
+                li { "address observations: {current.observations}" }
+                li { "handle table reallocations (predicted): {growths}" }
+                li { class: if current.relocations.is_empty() { "outcome-hit" } else { "outcome-different" },
+                    "element relocations: {current.relocations.len()}"
+                }
+            }
+
+            LogView {
+                title: "Relocations",
+                empty: current.relocations.is_empty(),
+                placeholder: if current.observations == 0 {
+                    "Run the detector to watch element addresses."
+                } else {
+                    "No element moved: growth reallocates the table of handle pointers, not the elements."
+                },
+                for relocation in current.relocations.iter() {
+                    div { class: "log-entry outcome-different", key: "{relocation.tag}-{relocation.new_addr}",
+                        "tag {relocation.tag} at [{relocation.idx}]: {relocation.old_addr:#x} → {relocation.new_addr:#x}; "
+                        "suspect pushes: "
+                        {
+                            relocation
+                                .suspects
+                                .iter()
+                                .map(|push| {
+                                    format!(
+                                        "#{}{}",
+                                        push.seq,
+                                        if push.grew_table { " (grew table)" } else { "" },
+                                    )
+                                })
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
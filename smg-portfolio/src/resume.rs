@@ -0,0 +1,463 @@
+//! The resume behind `Hero`, the experience timeline and `/resume`.
+//!
+//! `assets/resume.toml` is embedded at build time and parsed on first use.
+//! Unknown keys, malformed dates and broken links are rejected with the
+//! path of the offending entry, and `Hero` shows that error instead of a
+//! half-filled profile. `to_json_resume` exports the same data in the
+//! JSON Resume schema (https://jsonresume.org/schema).
+
+use std::fmt;
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+
+const SOURCE_PATH: &str = "assets/resume.toml";
+const SOURCE: &str = include_str!("../assets/resume.toml");
+pub const JSON_RESUME_FILE: &str = "resume.json";
+
+pub static RESUME: LazyLock<Result<Resume, String>> = LazyLock::new(|| {
+    let parsed = parse(SOURCE).map_err(|err| format!("{SOURCE_PATH}: {err}"));
+    if let Err(err) = &parsed {
+        tracing::error!(%err, "resume rejected");
+    }
+    parsed
+});
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Resume {
+    pub basics: Basics,
+    #[serde(default)]
+    pub experience: Vec<Experience>,
+    #[serde(default)]
+    pub skills: Vec<SkillGroup>,
+    #[serde(default)]
+    pub projects: Vec<Project>,
+    #[serde(default)]
+    pub links: Vec<Link>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Basics {
+    pub name: String,
+    pub headline: String,
+    pub location: String,
+    pub summary: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Experience {
+    pub role: String,
+    pub company: Option<String>,
+    /// How the role was held when there is no company, e.g. "Independent Contractor".
+    pub arrangement: Option<String>,
+    pub start: YearMonth,
+    /// `None` while the role is current.
+    pub end: Option<YearMonth>,
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub highlights: Vec<String>,
+}
+
+impl Experience {
+    /// "Software Engineer @ Feroworks", "Software Engineer as Independent Contractor".
+    pub fn title(&self) -> String {
+        let mut title = self.role.clone();
+        if let Some(company) = &self.company {
+            title.push_str(&format!(" @ {company}"));
+        }
+        if let Some(arrangement) = &self.arrangement {
+            title.push_str(&format!(" as {arrangement}"));
+        }
+        title
+    }
+
+    /// "OCT 2025--PRESENT", "JUL--OCT 2025", "DEC 2024--JAN 2025".
+    pub fn period(&self) -> String {
+        match self.end {
+            None => format!("{}--PRESENT", self.start),
+            Some(end) if end.year == self.start.year => {
+                format!("{}--{end}", self.start.month_abbr())
+            }
+            Some(end) => format!("{}--{end}", self.start),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SkillGroup {
+    pub name: String,
+    pub keywords: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Project {
+    pub name: String,
+    /// The project's page is `/projects/{slug}`.
+    pub slug: String,
+    pub summary: String,
+    /// Longer text for the project's page.
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub repo: Option<String>,
+    #[serde(default)]
+    pub status: ProjectStatus,
+    /// Tech tags; the gallery filters on these.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Live demos embedded in the project's page.
+    #[serde(default)]
+    pub demos: Vec<DemoEmbed>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectStatus {
+    #[default]
+    Active,
+    Stable,
+    Experimental,
+    Archived,
+}
+
+impl ProjectStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            ProjectStatus::Active => "active",
+            ProjectStatus::Stable => "stable",
+            ProjectStatus::Experimental => "experimental",
+            ProjectStatus::Archived => "archived",
+        }
+    }
+
+    pub fn badge_class(self) -> &'static str {
+        match self {
+            ProjectStatus::Active => "spec-badge new-badge",
+            ProjectStatus::Stable => "spec-badge status-stable",
+            ProjectStatus::Experimental => "spec-badge status-experimental",
+            ProjectStatus::Archived => "spec-badge status-archived",
+        }
+    }
+}
+
+/// An interactive component a project page can embed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DemoEmbed {
+    ContiguousArray,
+    LinkedStack,
+}
+
+impl Resume {
+    pub fn project(&self, slug: &str) -> Option<&Project> {
+        self.projects.iter().find(|project| project.slug == slug)
+    }
+
+    /// Every project tag once, in first-seen order.
+    pub fn project_tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = Vec::new();
+        for tag in self.projects.iter().flat_map(|project| &project.keywords) {
+            if !tags.contains(&tag.as_str()) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Link {
+    pub label: String,
+    /// The profile's network for JSON Resume, e.g. "GitHub"; defaults to `label`.
+    pub network: Option<String>,
+    pub url: String,
+}
+
+/// A `YYYY-MM` date, which is all a CV needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct YearMonth {
+    pub year: u16,
+    pub month: u8,
+}
+
+const MONTHS: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+
+impl YearMonth {
+    pub fn month_abbr(self) -> &'static str {
+        MONTHS[usize::from(self.month - 1)]
+    }
+}
+
+impl TryFrom<String> for YearMonth {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let invalid = || format!("`{text}` is not a YYYY-MM date");
+        let (year, month) = text.split_once('-').ok_or_else(invalid)?;
+        if year.len() != 4 || month.len() != 2 {
+            return Err(invalid());
+        }
+        let year = year.parse().map_err(|_| invalid())?;
+        let month = month.parse().map_err(|_| invalid())?;
+        if !(1..=12).contains(&month) {
+            return Err(format!("`{text}` has no month {month}"));
+        }
+        Ok(YearMonth { year, month })
+    }
+}
+
+impl From<YearMonth> for String {
+    fn from(date: YearMonth) -> String {
+        format!("{:04}-{:02}", date.year, date.month)
+    }
+}
+
+impl fmt::Display for YearMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.month_abbr(), self.year)
+    }
+}
+
+fn check_url(at: &str, url: &str) -> Result<(), String> {
+    if url.starts_with("https://") || url.starts_with("http://") || url.starts_with("mailto:") {
+        Ok(())
+    } else {
+        Err(format!("{at}: `{url}` is not an http(s) or mailto link"))
+    }
+}
+
+fn check_text(at: &str, text: &str) -> Result<(), String> {
+    if text.trim().is_empty() {
+        Err(format!("{at} is empty"))
+    } else {
+        Ok(())
+    }
+}
+
+impl Resume {
+    pub fn to_json_resume(&self) -> String {
+        serde_json::to_string_pretty(&json_resume::Document::from(self)).expect("a resume always serializes")
+    }
+}
+
+/// The subset of the JSON Resume schema this model fills.
+mod json_resume {
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    pub struct Document {
+        basics: Basics,
+        work: Vec<Work>,
+        skills: Vec<Skill>,
+        projects: Vec<Project>,
+    }
+
+    #[derive(Serialize)]
+    struct Basics {
+        name: String,
+        label: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        email: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        summary: Option<String>,
+        location: Location,
+        profiles: Vec<Profile>,
+    }
+
+    #[derive(Serialize)]
+    struct Location {
+        address: String,
+        city: String,
+    }
+
+    #[derive(Serialize)]
+    struct Profile {
+        network: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        username: Option<String>,
+        url: String,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Work {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        position: String,
+        start_date: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        end_date: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        summary: Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        highlights: Vec<String>,
+    }
+
+    #[derive(Serialize)]
+    struct Skill {
+        name: String,
+        keywords: Vec<String>,
+    }
+
+    #[derive(Serialize)]
+    struct Project {
+        name: String,
+        description: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        keywords: Vec<String>,
+    }
+
+    impl From<&super::Resume> for Document {
+        fn from(resume: &super::Resume) -> Self {
+            let basics = &resume.basics;
+            Document {
+                basics: Basics {
+                    name: basics.name.clone(),
+                    label: basics.headline.clone(),
+                    email: basics.email.clone(),
+                    summary: basics.summary.clone(),
+                    location: Location {
+                        address: basics.location.clone(),
+                        city: basics.location.split(',').next().unwrap_or_default().trim().to_string(),
+                    },
+                    profiles: resume
+                        .links
+                        .iter()
+                        .map(|link| Profile {
+                            network: link.network.clone().unwrap_or_else(|| link.label.clone()),
+                            username: link
+                                .url
+                                .trim_end_matches('/')
+                                .rsplit('/')
+                                .next()
+                                .filter(|_| link.url.matches('/').count() > 2)
+                                .map(str::to_string),
+                            url: link.url.clone(),
+                        })
+                        .collect(),
+                },
+                work: resume
+                    .experience
+                    .iter()
+                    .map(|job| Work {
+                        name: job.company.clone().or_else(|| job.arrangement.clone()),
+                        position: job.role.clone(),
+                        start_date: job.start.into(),
+                        end_date: job.end.map(String::from),
+                        summary: job.summary.clone(),
+                        highlights: job.highlights.clone(),
+                    })
+                    .collect(),
+                skills: resume
+                    .skills
+                    .iter()
+                    .map(|group| Skill {
+                        name: group.name.clone(),
+                        keywords: group.keywords.clone(),
+                    })
+                    .collect(),
+                projects: resume
+                    .projects
+                    .iter()
+                    .map(|project| Project {
+                        name: project.name.clone(),
+                        description: project.summary.clone(),
+                        url: project.url.clone().or_else(|| project.repo.clone()),
+                        keywords: project.keywords.clone(),
+                    })
+                    .collect(),
+            }
+        }
+    }
+}
+
+/// Parses and checks a resume, with the experience sorted newest first.
+pub fn parse(source: &str) -> Result<Resume, String> {
+    let mut resume: Resume = toml::from_str(source).map_err(|err| err.to_string())?;
+
+    check_text("basics.name", &resume.basics.name)?;
+    check_text("basics.headline", &resume.basics.headline)?;
+    for (i, job) in resume.experience.iter().enumerate() {
+        check_text(&format!("experience[{i}].role"), &job.role)?;
+        if let Some(end) = job.end.filter(|end| *end < job.start) {
+            return Err(format!("experience[{i}] ends ({}) before it starts ({})", String::from(end), String::from(job.start)));
+        }
+    }
+    for (i, group) in resume.skills.iter().enumerate() {
+        check_text(&format!("skills[{i}].name"), &group.name)?;
+    }
+    for (i, project) in resume.projects.iter().enumerate() {
+        check_text(&format!("projects[{i}].name"), &project.name)?;
+        let slug = &project.slug;
+        if slug.is_empty() || !slug.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-') {
+            return Err(format!("projects[{i}].slug `{slug}` must be lowercase letters, digits and dashes"));
+        }
+        if resume.projects[..i].iter().any(|earlier| earlier.slug == *slug) {
+            return Err(format!("projects[{i}].slug `{slug}` is used twice"));
+        }
+        if let Some(url) = &project.url {
+            check_url(&format!("projects[{i}].url"), url)?;
+        }
+        if let Some(repo) = &project.repo {
+            check_url(&format!("projects[{i}].repo"), repo)?;
+        }
+        if let Some(tag) = project.keywords.iter().find(|tag| tag.trim().is_empty() || tag.contains('/')) {
+            return Err(format!("projects[{i}].keywords: `{tag}` must be non-empty and have no `/`"));
+        }
+    }
+    for (i, link) in resume.links.iter().enumerate() {
+        check_text(&format!("links[{i}].label"), &link.label)?;
+        check_url(&format!("links[{i}].url"), &link.url)?;
+    }
+
+    resume.experience.sort_by_key(|job| std::cmp::Reverse(job.start));
+    Ok(resume)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASICS: &str = "[basics]\nname = \"A\"\nheadline = \"B\"\nlocation = \"C\"\n";
+
+    fn project(slug: &str, extra: &str) -> String {
+        format!("[[projects]]\nname = \"P\"\nslug = \"{slug}\"\nsummary = \"S\"\n{extra}")
+    }
+
+    #[test]
+    fn bundled_resume_parses() {
+        let resume = RESUME.as_ref().unwrap_or_else(|err| panic!("{err}"));
+        assert!(resume.experience.windows(2).all(|pair| pair[0].start >= pair[1].start));
+        let json: serde_json::Value = serde_json::from_str(&resume.to_json_resume()).unwrap();
+        assert_eq!(json["basics"]["name"], resume.basics.name.as_str());
+    }
+
+    #[test]
+    fn rejects_bad_entries_with_their_path() {
+        let cases = [
+            (
+                "[[experience]]\nrole = \"R\"\nstart = \"2025-07\"\nend = \"2025-06\"\n".into(),
+                "experience[0] ends (2025-06)",
+            ),
+            ("[[experience]]\nrole = \"R\"\nstart = \"2025-13\"\n".into(), "2025-13"),
+            (project("Bad Slug", ""), "projects[0].slug `Bad Slug`"),
+            (project("p", "") + &project("p", ""), "projects[1].slug `p` is used twice"),
+            (project("p", "keywords = [\"a/b\"]\n"), "projects[0].keywords"),
+            ("[[links]]\nlabel = \"L\"\nurl = \"ftp://x\"\n".into(), "links[0].url"),
+            ("[[skills]]\nname = \"S\"\nkeywords = []\nlevel = 3\n".into(), "level"),
+        ];
+        for (entries, expected) in cases {
+            let err = parse(&format!("{BASICS}{entries}")).unwrap_err();
+            assert!(err.contains(expected), "{expected}: {err}");
+        }
+    }
+}
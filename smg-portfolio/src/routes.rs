@@ -0,0 +1,915 @@
+//! Pages and client-side routes.
+//!
+//! Paths are relative to `base_path` in Dioxus.toml (`/portfolio/`); the
+//! router adds and strips it. `dx build --ssg` renders every path from
+//! `static_routes` to its own `index.html`, so GitHub Pages serves real HTML
+//! for a deep link such as `/portfolio/demos/linked-stack` and the wasm
+//! bundle hydrates it. Demos show a `DemoSlot` preview until then.
+//!
+//! Any other path gets `404.html` from `discovery`, the SPA fallback. It
+//! cannot be a copy of a pre-rendered page, which would hydrate against the
+//! wrong route, so it stores the path in `sessionStorage` and loads the app
+//! at `/`. `Layout` picks the path up once hydrated and routes to it, which
+//! ends on `NotFound` for paths no route matches. The fragment is not
+//! carried over.
+
+use dioxus::prelude::*;
+
+use crate::blog::{self, POSTS};
+use crate::boundary::BoundaryExplorer;
+use crate::console::ConsolePanel;
+use crate::demos::{ArrayDemo, DemoSlot, EmbeddedDemo, StackDemo};
+use crate::filters::FilterDemo;
+use crate::gallery::AlgorithmGallery;
+use crate::ledger::LedgerDemo;
+use crate::life::LifeDemo;
+use crate::markdown::MarkdownBlocks;
+use crate::pipeline::PipelineBuilder;
+use crate::pop_errors::ErrorPanel;
+use crate::relocation::RelocationDetector;
+use crate::resume::{self, Project, RESUME};
+use crate::scenario::ScenarioPanel;
+use crate::script::ScriptEditor;
+use crate::seo::{PageMeta, PersonSchema};
+use crate::telemetry::TraceViewer;
+use crate::theme::ThemeToggle;
+use crate::ui::{Button, Chip, ChipVariant, Variant};
+use crate::{browser, Session};
+
+const RESUME_CSS: Asset = asset!("/assets/resume.css");
+
+#[derive(Clone, Debug, PartialEq, Routable)]
+#[rustfmt::skip]
+pub enum Route {
+    #[layout(Layout)]
+        #[route("/")]
+        Profile {},
+        #[route("/demos/contiguous-array")]
+        ContiguousArrayDemo {},
+        #[route("/demos/linked-stack")]
+        LinkedStackDemo {},
+        #[route("/projects")]
+        Projects {},
+        #[route("/projects/tags/:tag")]
+        ProjectsTag { tag: String },
+        #[route("/projects/:slug")]
+        ProjectPage { slug: String },
+        #[route("/writing")]
+        Writing {},
+        #[route("/writing/tags/:tag")]
+        WritingTag { tag: String },
+        #[route("/writing/:slug")]
+        WritingPost { slug: String },
+        #[route("/resume", ResumePage)]
+        Resume {},
+        #[route("/:..segments")]
+        NotFound { segments: Vec<String> },
+}
+
+/// Every page worth listing in a sitemap: the static routes plus a page per
+/// project, post and tag.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn site_routes() -> Vec<Route> {
+    let mut routes = Route::static_routes();
+    if let Ok(resume) = &*RESUME {
+        routes.extend(resume.projects.iter().map(|project| Route::ProjectPage { slug: project.slug.clone() }));
+        routes.extend(resume.project_tags().into_iter().map(|tag| Route::ProjectsTag { tag: tag.to_string() }));
+    }
+    if let Ok(posts) = &*POSTS {
+        let published: Vec<blog::Post> = posts.iter().filter(|post| !post.draft).cloned().collect();
+        routes.extend(published.iter().map(|post| Route::WritingPost { slug: post.slug.clone() }));
+        routes.extend(blog::tags(&published).into_iter().map(|tag| Route::WritingTag { tag: tag.to_string() }));
+    }
+    routes
+}
+
+/// Where a pre-rendered route is written, relative to `public/`: the
+/// segments percent-decoded, because GitHub Pages looks files up by the
+/// decoded path, so `/projects/tags/Data%20structures` is served from
+/// `projects/tags/Data structures/index.html`.
+#[cfg_attr(not(feature = "server"), allow(dead_code))]
+pub fn page_dir(route: &str) -> std::path::PathBuf {
+    let path = route.split(['?', '#']).next().unwrap_or_default();
+    path.split('/').filter(|segment| !segment.is_empty()).map(decode_segment).collect()
+}
+
+fn decode_segment(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).filter(|hex| hex.iter().all(u8::is_ascii_hexdigit));
+        match (bytes[i], hex) {
+            (b'%', Some(hex)) => {
+                out.push(u8::from_str_radix(std::str::from_utf8(hex).expect("hex digits"), 16).expect("hex digits"));
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// The pages `dx build --ssg` asks the server build to pre-render.
+#[server(endpoint = "static_routes")]
+async fn static_routes() -> ServerFnResult<Vec<String>> {
+    Ok(site_routes().iter().map(ToString::to_string).collect())
+}
+
+/// In-page shortcuts on the ContiguousArray page: (area id, label).
+const ARRAY_SECTIONS: [(&str, &str); 13] = [
+    ("array-area", "matrix"),
+    ("scenario-area", "scenarios"),
+    ("script-area", "scripts"),
+    ("console-area", "console"),
+    ("gallery-area", "algorithms"),
+    ("pipeline-area", "pipeline"),
+    ("boundary-area", "boundaries"),
+    ("relocation-area", "relocation"),
+    ("ledger-area", "ledger"),
+    ("life-area", "life"),
+    ("filter-area", "filters"),
+    ("error-area", "pop errors"),
+    ("trace-area", "traces"),
+];
+
+/// Navigation and footer around every page. Owns the `Session`, so demo
+/// state, the link or saved-state restore and autosaving outlive
+/// navigation.
+#[component]
+fn Layout() -> Element {
+    let mut session = use_context_provider(Session::new);
+    // Effects only run in the browser, after the pre-rendered page has been
+    // hydrated, so the static build never starts timers or reads storage.
+    use_effect(move || {
+        session.hydrated.set(true);
+        // Sent here by `404.html`: the page hydrated as `/`, now route to
+        // the path that was asked for.
+        if let Some(route) = browser::take_deep_link().and_then(|path| path.parse::<Route>().ok()) {
+            navigator().replace(route);
+        }
+        spawn(session.run());
+    });
+
+    rsx! {
+        nav { class: "site-nav",
+            Link { to: Route::Profile {}, active_class: "active", "PROFILE" }
+            Link { to: Route::ContiguousArrayDemo {}, active_class: "active", "CONTIGUOUS ARRAY" }
+            Link { to: Route::LinkedStackDemo {}, active_class: "active", "LINKED STACK" }
+            Link { to: Route::Projects {}, active_class: "active", "PROJECTS" }
+            Link { to: Route::Writing {}, active_class: "active", "WRITING" }
+            Link { to: Route::Resume {}, active_class: "active", "RESUME" }
+            ThemeToggle {}
+        }
+
+        Outlet::<Route> {}
+
+        Footer {}
+    }
+}
+
+#[component]
+fn Profile() -> Element {
+    rsx! {
+        PageMeta { route: Route::Profile {} }
+        PersonSchema {}
+        Hero {}
+
+        main { class: "container",
+            div { class: "page-index",
+                Link { class: "page-card", to: Route::ContiguousArrayDemo {},
+                    h3 { "ContiguousArray" }
+                    p { "Concurrent index access, scripts, a console, parallel algorithms, pipelines, Life and image filters." }
+                }
+                Link { class: "page-card", to: Route::LinkedStackDemo {},
+                    h3 { "LinkedStack" }
+                    p { "A counter that grows with every push, under bursts of concurrent workers." }
+                }
+                Link { class: "page-card", to: Route::Projects {},
+                    h3 { "Projects" }
+                    p { "Everything else, filterable by tag, with live demos where there are any." }
+                }
+                Link { class: "page-card", to: Route::Writing {},
+                    h3 { "Writing" }
+                    p { "Notes on concurrency-first data structures." }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ContiguousArrayDemo() -> Element {
+    let session = use_context::<Session>();
+    let Session {
+        env,
+        pipeline_config,
+        pipeline_stages,
+        ..
+    } = session;
+
+    rsx! {
+        PageMeta {
+            title: "ContiguousArray",
+            description: "Live rsmg_core ContiguousArray demos: concurrent index access, scripts, a console, \
+                parallel algorithms, pipelines, Life and image filters.",
+            route: Route::ContiguousArrayDemo {},
+        }
+        main { class: "container",
+            nav { class: "page-sections",
+                for (id , label) in ARRAY_SECTIONS {
+                    a { href: "#{id}", "{label}" }
+                }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // CONTIGUOUSARRAY DEMO - True Concurrency First
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "array-area", label: "ENGINE:", value: "rsmg_core::prim::array::ContiguousArray",
+                ArrayDemo { env }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // SCENARIOS - save, load and share the whole demo state
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "scenario-area", label: "SCENARIO:", value: "save, load and share the demo state",
+                ScenarioPanel { session }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // SCRIPT EDITOR - scenario DSL behind the array buttons
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "script-area", label: "SCRIPT:", value: "scenario DSL, parsed in Rust",
+                ScriptEditor { env }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // CONSOLE - type commands against both singletons
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "console-area", label: "CONSOLE:", value: "push, pop, inspect, len, burst, drain",
+                ConsolePanel { env }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // ALGORITHM GALLERY - phased workers on the same array
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "gallery-area", label: "GALLERY:", value: "phased workers, one barrier per phase",
+                AlgorithmGallery { array_items: env.array_items }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // PIPELINE BUILDER - compose stages, watch backpressure
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "pipeline-area", label: "PIPELINE:", value: "composed stages with backpressure",
+                PipelineBuilder { config: pipeline_config, stages: pipeline_stages, pop_errors: env.pop_errors }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // BOUNDARY EXPLORER - stale indices vs concurrent pop
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "boundary-area", label: "EXPLORER:", value: "inspect_element(stale_idx) vs pop()",
+                BoundaryExplorer { pop_errors: env.pop_errors }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // RELOCATION DETECTOR - element addresses during growth
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "relocation-area", label: "DETECTOR:", value: "element addresses across push growth",
+                RelocationDetector { pop_errors: env.pop_errors }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // LEDGER - multi-index invariants
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "ledger-area", label: "LEDGER:", value: "multi-index invariants",
+                LedgerDemo { pop_errors: env.pop_errors }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // GAME OF LIFE - 2D stencil over ContiguousArray
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "life-area", label: "MATRIX:", value: "Matrix2D<u8> over ContiguousArray",
+                LifeDemo { pop_errors: env.pop_errors }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // IMAGE FILTERS - tiled workers over 100k+ pixels
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "filter-area", label: "PIXELS:", value: "ContiguousArray<u32>, one RGBA pixel per element",
+                FilterDemo { pop_errors: env.pop_errors }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // POP ERRORS
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "error-area", label: "POP ERRORS:", value: "per-worker pop failures",
+                ErrorPanel { pop_errors: env.pop_errors }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // TRACE VIEWER
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "trace-area", label: "TRACING:", value: "tracing + in-page subscriber",
+                TraceViewer {}
+            }
+        }
+    }
+}
+
+#[component]
+fn LinkedStackDemo() -> Element {
+    let Session { env, .. } = use_context::<Session>();
+
+    rsx! {
+        PageMeta {
+            title: "LinkedStack",
+            description: "A live rsmg_core LinkedStack demo: a counter that grows with every push, \
+                under bursts of concurrent workers.",
+            route: Route::LinkedStackDemo {},
+        }
+        main { class: "container",
+            // ═══════════════════════════════════════════════════════
+            // LINKEDSTACK DEMO (existing)
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "magic-area", label: "ENGINE:", value: "rsmg_core::prim::stack::LinkedStack",
+                StackDemo { env }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // CONSOLE - `use stack` works on MAGIC_DATA
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "console-area", label: "CONSOLE:", value: "push, pop, inspect, len, burst, drain",
+                ConsolePanel { env }
+            }
+
+            // ═══════════════════════════════════════════════════════
+            // POP ERRORS
+            // ═══════════════════════════════════════════════════════
+            DemoSlot { area: "error-area", label: "POP ERRORS:", value: "per-worker pop failures",
+                ErrorPanel { pop_errors: env.pop_errors }
+            }
+        }
+    }
+}
+
+#[component]
+fn Projects() -> Element {
+    rsx! {
+        ProjectGallery { tag: String::new() }
+    }
+}
+
+#[component]
+fn ProjectsTag(tag: String) -> Element {
+    rsx! {
+        ProjectGallery { tag }
+    }
+}
+
+/// Project cards from `[[projects]]` in resume.toml. `tag` narrows them to
+/// one tech tag; empty shows all.
+#[component]
+fn ProjectGallery(tag: String) -> Element {
+    let resume = match &*RESUME {
+        Ok(resume) => resume,
+        Err(err) => {
+            return rsx! {
+                main { class: "container",
+                    div { class: "page-intro",
+                        h2 { "Projects unavailable" }
+                        div { class: "array-empty outcome-different resume-error", "{err}" }
+                    }
+                }
+            }
+        }
+    };
+    let shown: Vec<&Project> =
+        resume.projects.iter().filter(|project| tag.is_empty() || project.keywords.contains(&tag)).collect();
+    let (title, description, route) = if tag.is_empty() {
+        (
+            "Projects".to_string(),
+            format!("Projects by {}, with live demos where there are any.", resume.basics.name),
+            Route::Projects {},
+        )
+    } else {
+        (
+            format!("Projects · {tag}"),
+            format!("Projects by {} tagged {tag}.", resume.basics.name),
+            Route::ProjectsTag { tag: tag.clone() },
+        )
+    };
+
+    rsx! {
+        PageMeta { title, description, route }
+        main { class: "container",
+            div { class: "page-intro",
+                h2 { "Projects" }
+                p { "Every demo on this site runs on rsmg_core, compiled to WebAssembly with Dioxus." }
+                nav { class: "spec-features project-tags",
+                    Link {
+                        class: if tag.is_empty() { "feature-chip active" } else { "feature-chip" },
+                        to: Route::Projects {},
+                        "all"
+                    }
+                    for name in resume.project_tags() {
+                        Link {
+                            class: if name == tag { "feature-chip active" } else { "feature-chip" },
+                            to: Route::ProjectsTag { tag: name.to_string() },
+                            "{name}"
+                        }
+                    }
+                }
+            }
+
+            div { class: "project-grid",
+                if shown.is_empty() {
+                    div { class: "array-empty", "No project is tagged {tag}." }
+                }
+                for project in shown {
+                    ProjectCard { project: project.clone() }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ProjectCard(project: Project) -> Element {
+    rsx! {
+        article { class: "project-card",
+            header { class: "project-card-head",
+                Link { to: Route::ProjectPage { slug: project.slug.clone() },
+                    h3 { "{project.name}" }
+                }
+                span { class: project.status.badge_class(), "{project.status.label()}" }
+            }
+            p { "{project.summary}" }
+            div { class: "spec-features",
+                for tag in project.keywords.iter() {
+                    Link { class: "feature-chip", to: Route::ProjectsTag { tag: tag.clone() }, "{tag}" }
+                }
+            }
+            ProjectLinks { project: project.clone() }
+        }
+    }
+}
+
+#[component]
+fn ProjectLinks(project: Project) -> Element {
+    rsx! {
+        div { class: "project-links",
+            if let Some(repo) = &project.repo {
+                a { href: "{repo}", target: "_blank", rel: "noopener", "SOURCE" }
+            }
+            if let Some(url) = &project.url {
+                a { href: "{url}", target: "_blank", rel: "noopener", "VISIT" }
+            }
+            if !project.demos.is_empty() {
+                Link { to: Route::ProjectPage { slug: project.slug.clone() }, "LIVE DEMO" }
+            }
+        }
+    }
+}
+
+/// One project with its description and embedded demos. The demos share the
+/// layout's `Session`, so they show the same singletons as the demo pages.
+#[component]
+fn ProjectPage(slug: String) -> Element {
+    let Some(project) = RESUME.as_ref().ok().and_then(|resume| resume.project(&slug)) else {
+        return rsx! {
+            main { class: "container",
+                div { class: "page-intro",
+                    h2 { "Project not found" }
+                    p { "There is no project called {slug}." }
+                    Link { to: Route::Projects {}, "Back to the projects" }
+                }
+            }
+        };
+    };
+
+    rsx! {
+        PageMeta {
+            title: project.name.clone(),
+            description: project.summary.clone(),
+            route: Route::ProjectPage { slug: slug.clone() },
+        }
+        main { class: "container",
+            div { class: "page-intro project-intro",
+                h2 {
+                    "{project.name} "
+                    span { class: project.status.badge_class(), "{project.status.label()}" }
+                }
+                p { {project.description.as_deref().unwrap_or(&project.summary)} }
+                div { class: "spec-features",
+                    for tag in project.keywords.iter() {
+                        Link { class: "feature-chip", to: Route::ProjectsTag { tag: tag.clone() }, "{tag}" }
+                    }
+                }
+                ProjectLinks { project: project.clone() }
+            }
+
+            for demo in project.demos.iter().copied() {
+                EmbeddedDemo { demo }
+            }
+        }
+    }
+}
+
+#[component]
+fn Writing() -> Element {
+    rsx! {
+        PostIndex { tag: String::new() }
+    }
+}
+
+#[component]
+fn WritingTag(tag: String) -> Element {
+    rsx! {
+        PostIndex { tag }
+    }
+}
+
+/// Posts from `content/`, newest first; `tag` narrows them, empty shows all.
+#[component]
+fn PostIndex(tag: String) -> Element {
+    let posts = match &*POSTS {
+        Ok(posts) => posts,
+        Err(err) => {
+            return rsx! {
+                main { class: "container",
+                    div { class: "page-intro",
+                        h2 { "Writing unavailable" }
+                        div { class: "array-empty outcome-different resume-error", "{err}" }
+                    }
+                }
+            }
+        }
+    };
+    let shown: Vec<&blog::Post> =
+        posts.iter().filter(|post| tag.is_empty() || post.tags.contains(&tag)).collect();
+    let (title, description, route) = if tag.is_empty() {
+        (
+            "Writing".to_string(),
+            "Notes on concurrency-first data structures, next to the demos they describe.".to_string(),
+            Route::Writing {},
+        )
+    } else {
+        (format!("Writing · {tag}"), format!("Posts tagged {tag}."), Route::WritingTag { tag: tag.clone() })
+    };
+
+    rsx! {
+        PageMeta { title, description, route }
+        main { class: "container",
+            div { class: "page-intro",
+                h2 {
+                    "Writing"
+                    if !tag.is_empty() {
+                        " · {tag}"
+                    }
+                }
+                p { "Notes on concurrency-first data structures, next to the demos they describe." }
+                nav { class: "spec-features",
+                    Link {
+                        class: if tag.is_empty() { "feature-chip active" } else { "feature-chip" },
+                        to: Route::Writing {},
+                        "all"
+                    }
+                    for name in blog::tags(posts) {
+                        Link {
+                            class: if name == tag { "feature-chip active" } else { "feature-chip" },
+                            to: Route::WritingTag { tag: name.to_string() },
+                            "{name}"
+                        }
+                    }
+                }
+            }
+
+            div { class: "post-list",
+                if shown.is_empty() {
+                    div { class: "array-empty", "Nothing published yet." }
+                }
+                for post in shown {
+                    article { class: "post-card",
+                        Link { to: Route::WritingPost { slug: post.slug.clone() },
+                            h3 { "{post.title}" }
+                        }
+                        PostMeta { post: post.clone() }
+                        if let Some(summary) = &post.summary {
+                            p { "{summary}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn PostMeta(post: blog::Post) -> Element {
+    rsx! {
+        div { class: "post-meta",
+            time { datetime: "{post.date}", "{post.date}" }
+            if post.draft {
+                span { class: "spec-badge status-experimental", "draft" }
+            }
+            for tag in post.tags.iter() {
+                Link { class: "feature-chip", to: Route::WritingTag { tag: tag.clone() }, "{tag}" }
+            }
+        }
+    }
+}
+
+#[component]
+fn WritingPost(slug: String) -> Element {
+    if let Err(err) = &*POSTS {
+        return rsx! {
+            main { class: "container",
+                div { class: "page-intro",
+                    h2 { "Writing unavailable" }
+                    div { class: "array-empty outcome-different resume-error", "{err}" }
+                }
+            }
+        };
+    }
+    let Some(post) = blog::post(&slug) else {
+        return rsx! {
+            main { class: "container",
+                div { class: "page-intro",
+                    h2 { "Post not found" }
+                    p { "There is no post called {slug}." }
+                    Link { to: Route::Writing {}, "Back to writing" }
+                }
+            }
+        };
+    };
+
+    rsx! {
+        PageMeta {
+            title: post.title.clone(),
+            description: post.summary.clone().unwrap_or_default(),
+            route: Route::WritingPost { slug: slug.clone() },
+            published: post.date.clone(),
+        }
+        main { class: "container",
+            article { class: "page-intro post",
+                header {
+                    h2 { "{post.title}" }
+                    PostMeta { post: post.clone() }
+                }
+                MarkdownBlocks { blocks: post.body.clone() }
+            }
+        }
+    }
+}
+
+/// The resume as one printable sheet. `resume.css` drops the navigation
+/// and the buttons on paper.
+#[component]
+fn ResumePage() -> Element {
+    let mut download_error = use_signal(|| None::<String>);
+    let resume = match &*RESUME {
+        Ok(resume) => resume,
+        Err(err) => {
+            return rsx! {
+                main { class: "container",
+                    div { class: "page-intro",
+                        h2 { "Resume unavailable" }
+                        div { class: "array-empty outcome-different resume-error", "{err}" }
+                    }
+                }
+            }
+        }
+    };
+    let basics = &resume.basics;
+
+    rsx! {
+        PageMeta {
+            title: "Resume",
+            description: format!("{}, {} in {}: experience and skills.", basics.name, basics.headline, basics.location),
+            route: Route::Resume {},
+        }
+        PersonSchema {}
+        document::Link { rel: "stylesheet", href: RESUME_CSS }
+
+        main { class: "container",
+            div { class: "resume-actions",
+                Button { variant: Variant::Seed, onclick: move |_| browser::print(), "PRINT / SAVE AS PDF" }
+                Button {
+                    onclick: move |_| {
+                        let saved = RESUME
+                            .as_ref()
+                            .map_err(String::clone)
+                            .and_then(|resume| {
+                                browser::download(resume::JSON_RESUME_FILE, "application/json", &resume.to_json_resume())
+                            });
+                        download_error.set(saved.err());
+                    },
+                    "DOWNLOAD JSON RESUME"
+                }
+            }
+            if let Some(err) = download_error() {
+                div { class: "array-empty outcome-different", "{err}" }
+            }
+
+            article { class: "resume-sheet",
+                header { class: "resume-head",
+                    h1 { "{basics.name}" }
+                    p { class: "resume-headline",
+                        "{basics.headline} · {basics.location}"
+                        if let Some(email) = &basics.email {
+                            " · {email}"
+                        }
+                    }
+                    p { class: "resume-links",
+                        for link in resume.links.iter() {
+                            a { href: "{link.url}", "{link.url}" }
+                        }
+                    }
+                }
+
+                if let Some(summary) = &basics.summary {
+                    section {
+                        h2 { "Summary" }
+                        p { "{summary}" }
+                    }
+                }
+
+                if !resume.experience.is_empty() {
+                    section {
+                        h2 { "Experience" }
+                        for job in resume.experience.iter() {
+                            div { class: "resume-entry",
+                                div { class: "resume-entry-head",
+                                    strong { "{job.title()}" }
+                                    span { "{job.period()}" }
+                                }
+                                if let Some(summary) = &job.summary {
+                                    p { "{summary}" }
+                                }
+                                if !job.highlights.is_empty() {
+                                    ul {
+                                        for highlight in job.highlights.iter() {
+                                            li { "{highlight}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if !resume.skills.is_empty() {
+                    section {
+                        h2 { "Skills" }
+                        for group in resume.skills.iter() {
+                            p {
+                                strong { "{group.name}: " }
+                                {group.keywords.join(", ")}
+                            }
+                        }
+                    }
+                }
+
+                if !resume.projects.is_empty() {
+                    section {
+                        h2 { "Projects" }
+                        for project in resume.projects.iter() {
+                            div { class: "resume-entry",
+                                div { class: "resume-entry-head",
+                                    strong { "{project.name}" }
+                                    if let Some(url) = &project.url {
+                                        a { href: "{url}", "{url}" }
+                                    }
+                                }
+                                p { "{project.summary}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn NotFound(segments: Vec<String>) -> Element {
+    let path = segments.join("/");
+
+    rsx! {
+        PageMeta {
+            title: "Page not found",
+            description: "There is nothing at /{path}.",
+            route: Route::NotFound { segments: segments.clone() },
+        }
+        main { class: "container",
+            div { class: "page-intro",
+                h2 { "Page not found" }
+                p { "There is nothing at /{path}." }
+                Link { to: Route::Profile {}, "Back to the profile" }
+            }
+        }
+    }
+}
+
+/// Name, headline, experience timeline and links, all from `resume::RESUME`.
+#[component]
+fn Hero() -> Element {
+    let resume = match &*resume::RESUME {
+        Ok(resume) => resume,
+        Err(err) => {
+            return rsx! {
+                header { id: "hero",
+                    div { class: "hero-content",
+                        h1 { "Resume unavailable" }
+                        div { class: "array-empty outcome-different resume-error", "{err}" }
+                    }
+                }
+            }
+        }
+    };
+    let basics = &resume.basics;
+
+    rsx! {
+        header { id: "hero",
+            div { class: "hero-content",
+                h1 { "{basics.name}" }
+                div { class: "hero-subtitle",
+                    p { "{basics.headline} | {basics.location}" }
+                }
+                if !resume.experience.is_empty() {
+                    div { class: "experience-timeline",
+                        for job in resume.experience.iter() {
+                            div { class: "exp-item",
+                                span { class: "exp-date", "{job.period()}" }
+                                span { class: "exp-role", "{job.title()}" }
+                            }
+                        }
+                    }
+                }
+                div { id: "links",
+                    for link in resume.links.iter() {
+                        Chip { variant: ChipVariant::Large, href: link.url.clone(), "{link.label}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn Footer() -> Element {
+    rsx! {
+        footer { class: "site-footer",
+            div { class: "footer-title",
+                a { href: "https://achsanalfitra.github.io/portfolio/", "smg-portfolio" }
+                " © 2026"
+            }
+            div { class: "footer-credits",
+                "by "
+                a { class: "footer-author", href: "https://github.com/achsanalfitra", "Alfitra Heydar Achsan" }
+                " · Licensed under "
+                a { class: "footer-license", href: "https://creativecommons.org/licenses/by-nc/4.0/", "CC BY-NC 4.0" }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_route_has_its_own_page() {
+        let routes = site_routes();
+        let dirs: Vec<_> = routes.iter().map(|route| page_dir(&route.to_string())).collect();
+        for (i, dir) in dirs.iter().enumerate() {
+            assert!(!dirs[..i].contains(dir), "{} is rendered twice", dir.display());
+        }
+        assert!(dirs.contains(&std::path::PathBuf::new()), "no page for /");
+        let tags = RESUME.as_ref().unwrap().project_tags();
+        for tag in tags {
+            let dir = page_dir(&Route::ProjectsTag { tag: tag.to_string() }.to_string());
+            assert!(dirs.contains(&dir), "no page for the {tag} tag");
+            assert_eq!(dir.file_name().and_then(|name| name.to_str()), Some(tag));
+        }
+    }
+
+    #[test]
+    fn deep_links_from_404_parse_back() {
+        let tag = "/projects/tags/Data%20structures".parse::<Route>();
+        assert!(matches!(tag, Ok(Route::ProjectsTag { tag }) if tag == "Data structures"));
+        let unknown = "/no/such/page".parse::<Route>();
+        assert!(matches!(unknown, Ok(Route::NotFound { segments }) if segments == ["no", "such", "page"]));
+    }
+
+    #[test]
+    fn page_dirs_are_decoded() {
+        assert_eq!(page_dir("/projects/tags/Data%20structures"), std::path::Path::new("projects/tags/Data structures"));
+        assert_eq!(page_dir("/writing/tags/caf%C3%A9?x=1#top"), std::path::Path::new("writing/tags/café"));
+        assert_eq!(page_dir("/odd/100%25/%zz"), std::path::Path::new("odd/100%/%zz"));
+        assert_eq!(page_dir("/"), std::path::PathBuf::new());
+    }
+}
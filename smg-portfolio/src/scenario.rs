@@ -0,0 +1,366 @@
+//! Saving, loading and sharing the demo state.
+//!
+//! A `Scenario` holds everything needed to put the page back the way it was:
+//! both singletons, the chaos settings including the generator state, the
+//! pipeline builder's workers and the operation log. It travels as a JSON
+//! file or, base64url-encoded, in the URL fragment of a link to the
+//! portfolio (`/portfolio/demos/contiguous-array#scenario=...`), which is
+//! read once on load, whatever the page.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::chaos::{self, FaultMode};
+use crate::pipeline::{self, PipelineConfig, StageSpec};
+use crate::routes::Route;
+use crate::ui::{Button, Panel, SpecCard, Variant};
+use crate::{
+    array_pop, array_push, browser, persist, push_log, read_snapshot, stack_pop, stack_push, stack_snapshot, DemoEnv,
+    Session, RACE_LOG_CAPACITY,
+};
+
+pub const VERSION: u32 = 1;
+/// Where the demo page is served; see `base_path` in Dioxus.toml.
+pub const BASE_PATH: &str = "/portfolio/";
+pub const FILE_NAME: &str = "smg-scenario.json";
+const FRAGMENT_KEY: &str = "scenario=";
+/// Longest array or stack a scenario may restore.
+const MAX_ITEMS: usize = 4_096;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChaosSettings {
+    pub mode: FaultMode,
+    pub rate: u64,
+    pub seed: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scenario {
+    pub version: u32,
+    pub array: Vec<i32>,
+    /// Bottom of the stack first.
+    pub stack: Vec<i32>,
+    pub chaos: ChaosSettings,
+    pub pipeline: PipelineConfig,
+    pub stages: Vec<StageSpec>,
+    #[serde(default)]
+    pub log: Vec<String>,
+}
+
+impl Scenario {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a scenario always serializes")
+    }
+
+    pub fn from_json(json: &str) -> Result<Scenario, String> {
+        let scenario: Scenario = serde_json::from_str(json).map_err(|err| format!("not a scenario: {err}"))?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// The part after `#` in a share link.
+    pub fn to_fragment(&self) -> String {
+        let json = serde_json::to_string(self).expect("a scenario always serializes");
+        format!("{FRAGMENT_KEY}{}", URL_SAFE_NO_PAD.encode(json))
+    }
+
+    /// `None` when the fragment carries no scenario at all.
+    pub fn from_fragment(fragment: &str) -> Option<Result<Scenario, String>> {
+        let encoded = fragment.trim_start_matches('#').strip_prefix(FRAGMENT_KEY)?;
+        Some(
+            URL_SAFE_NO_PAD
+                .decode(encoded)
+                .map_err(|err| format!("the link's scenario is not valid base64url: {err}"))
+                .and_then(|bytes| String::from_utf8(bytes).map_err(|err| format!("the link's scenario is not text: {err}")))
+                .and_then(|json| Scenario::from_json(&json)),
+        )
+    }
+
+    /// A link to `page`, a route path such as `/demos/contiguous-array`.
+    pub fn share_link(&self, origin: &str, page: &str) -> String {
+        format!("{origin}{}{page}#{}", BASE_PATH.trim_end_matches('/'), self.to_fragment())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.version > VERSION {
+            return Err(format!("scenario version {} is newer than this page ({VERSION})", self.version));
+        }
+        for (name, len) in [("array", self.array.len()), ("stack", self.stack.len())] {
+            if len > MAX_ITEMS {
+                return Err(format!("the {name} holds {len} items; a scenario keeps at most {MAX_ITEMS}"));
+            }
+        }
+        if !self.chaos.mode.supported() {
+            return Err(format!("chaos mode `{}` is not available in this build", self.chaos.mode.label()));
+        }
+        if self.chaos.rate == 0 {
+            return Err("the chaos rate must be at least 1".to_string());
+        }
+        pipeline::validate(&self.pipeline, &self.stages)
+    }
+}
+
+/// The current state. Fails, rather than writing something `from_json`
+/// would refuse to load, when it breaks the same limits.
+pub fn capture(env: DemoEnv, config: Signal<PipelineConfig>, stages: Signal<Vec<StageSpec>>) -> Result<Scenario, String> {
+    let scenario = Scenario {
+        version: VERSION,
+        array: read_snapshot(),
+        stack: stack_snapshot(),
+        chaos: ChaosSettings {
+            mode: chaos::mode(),
+            rate: chaos::rate(),
+            seed: chaos::seed(),
+        },
+        pipeline: config(),
+        stages: stages(),
+        log: env.race_log.read().clone(),
+    };
+    scenario.validate().map_err(|err| format!("cannot save this state: {err}"))?;
+    Ok(scenario)
+}
+
+/// Replaces the current state with `scenario`. The scenario and the array
+/// are checked before anything changes; if a singleton still refuses midway,
+/// both are put back as they were and the page is left alone.
+pub fn restore(
+    scenario: &Scenario,
+    env: DemoEnv,
+    mut config: Signal<PipelineConfig>,
+    mut stages: Signal<Vec<StageSpec>>,
+) -> Result<(), String> {
+    scenario.validate()?;
+    if chaos::array_poisoned() {
+        return Err("the array is poisoned by an earlier panic; reload the page first".to_string());
+    }
+
+    let (array, stack) = (read_snapshot(), stack_snapshot());
+    let replaced = replace_stack(&scenario.stack).and_then(|()| replace_array(&scenario.array));
+    if let Err(err) = replaced {
+        return Err(match replace_stack(&stack).and_then(|()| replace_array(&array)) {
+            Ok(()) => format!("{err}; the previous state was kept"),
+            Err(again) => format!("{err}; putting the previous state back failed too ({again})"),
+        });
+    }
+
+    chaos::set_mode(scenario.chaos.mode);
+    chaos::set_rate(scenario.chaos.rate);
+    chaos::set_seed(scenario.chaos.seed);
+    config.set(scenario.pipeline);
+    stages.set(scenario.stages.clone());
+
+    let DemoEnv {
+        mut race_log,
+        mut stack_items,
+        ..
+    } = env;
+    let skip = scenario.log.len().saturating_sub(RACE_LOG_CAPACITY);
+    race_log.set(scenario.log[skip..].to_vec());
+    let depth = scenario.stack.len();
+    stack_items.set(vec![depth as i32; depth]);
+    env.publish(crate::Target::Array);
+    info!(array = scenario.array.len(), stack = depth, "scenario restored");
+    Ok(())
+}
+
+/// Empties `CONTIGUOUS_ARRAY` and pushes `values` in order.
+fn replace_array(values: &[i32]) -> Result<(), String> {
+    while array_pop().map_err(|err| format!("array pop failed ({err})"))?.is_some() {}
+    for value in values {
+        if !array_push(*value) {
+            return Err("array push refused".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Empties `MAGIC_DATA` and pushes `values`, bottom first.
+fn replace_stack(values: &[i32]) -> Result<(), String> {
+    while stack_pop().map_err(|err| format!("stack pop failed ({err})"))?.is_some() {}
+    for value in values {
+        stack_push(*value).map_err(|err| format!("stack push failed ({err})"))?;
+    }
+    Ok(())
+}
+
+/// Restores the scenario in the page's URL fragment, if there is one.
+pub fn restore_from_location(
+    env: DemoEnv,
+    config: Signal<PipelineConfig>,
+    stages: Signal<Vec<StageSpec>>,
+) -> Option<Result<String, String>> {
+    let fragment = crate::browser::fragment()?;
+    let restored = Scenario::from_fragment(&fragment)?.and_then(|scenario| restore(&scenario, env, config, stages));
+    Some(match restored {
+        Ok(()) => Ok("restored the scenario from the link".to_string()),
+        Err(err) => {
+            warn!(%err, "scenario link ignored");
+            Err(format!("could not restore the link: {err}"))
+        }
+    })
+}
+
+/// Download, upload and link sharing for `Scenario`, and the switch for
+/// persistence across reloads.
+#[component]
+pub fn ScenarioPanel(session: Session) -> Element {
+    let Session {
+        env,
+        pipeline_config,
+        pipeline_stages,
+        mut remember,
+        scenario_status: mut status,
+        ..
+    } = session;
+    let mut link = use_signal(|| None::<String>);
+
+    let summary = format!(
+        "{} array element(s), stack depth {}, {} pipeline stage(s)",
+        env.array_items.read().len(),
+        env.stack_items.read().len(),
+        pipeline_stages.read().len()
+    );
+
+    rsx! {
+        Panel { id: "scenario-area",
+            SpecCard { label: "SCENARIO:", value: "{summary}",
+                "Save the array, the stack, the chaos settings and generator seed, the pipeline workers and the log "
+                "as JSON, or share them as a link. Opening the link puts the page back exactly as it was. "
+                "REMEMBER STATE keeps the same snapshot across reloads, in localStorage on the web "
+                "or the platform data directory on desktop."
+            }
+
+            div { class: "trace-toolbar",
+                Button {
+                    variant: Variant::Seed,
+                    onclick: move |_| {
+                        let saved = capture(env, pipeline_config, pipeline_stages)
+                            .and_then(|saved| browser::download(FILE_NAME, "application/json", &saved.to_json()));
+                        status.set(Some(saved.map(|()| format!("saved {}", FILE_NAME))));
+                    },
+                    "SAVE JSON"
+                }
+                label { class: "btn btn-seed scenario-file",
+                    "OPEN JSON…"
+                    input {
+                        r#type: "file",
+                        accept: ".json,application/json",
+                        onchange: move |evt| {
+                            let Some(file) = evt.files().into_iter().next() else {
+                                return;
+                            };
+                            spawn(async move {
+                                let loaded = file
+                                    .read_string()
+                                    .await
+                                    .map_err(|err| err.to_string())
+                                    .and_then(|json| Scenario::from_json(&json))
+                                    .and_then(|loaded| restore(&loaded, env, pipeline_config, pipeline_stages))
+                                    .map(|()| format!("loaded {}", file.name()))
+                                    .map_err(|err| format!("{}: {err}", file.name()));
+                                if let Ok(message) = &loaded {
+                                    push_log(env.race_log, format!("scenario: {message}"));
+                                }
+                                status.set(Some(loaded));
+                            });
+                        },
+                    }
+                }
+                Button {
+                    variant: Variant::Burst,
+                    onclick: move |_| {
+                        match capture(env, pipeline_config, pipeline_stages) {
+                            Ok(saved) => {
+                                browser::set_fragment(&saved.to_fragment());
+                                let page = Route::ContiguousArrayDemo {}.to_string();
+                                link.set(Some(saved.share_link(&browser::origin(), &page)));
+                                status.set(Some(Ok("the address bar now holds this scenario".to_string())));
+                            }
+                            Err(err) => status.set(Some(Err(err))),
+                        }
+                    },
+                    "SHARE LINK"
+                }
+                if remember() {
+                    Button {
+                        variant: Variant::Reset,
+                        onclick: move |_| {
+                            remember.set(false);
+                            status.set(Some(persist::forget().map(|()| "forgot the saved state".to_string())));
+                        },
+                        "FORGET STATE"
+                    }
+                } else {
+                    Button {
+                        onclick: move |_| {
+                            remember.set(true);
+                            if session.save() {
+                                status.set(Some(Ok("the state now survives a reload".to_string())));
+                            } else {
+                                remember.set(false);
+                            }
+                        },
+                        "REMEMBER STATE"
+                    }
+                }
+            }
+
+            match status() {
+                Some(Ok(message)) => rsx! {
+                    div { class: "array-header outcome-hit", "{message}" }
+                },
+                Some(Err(err)) => rsx! {
+                    div { class: "array-empty outcome-different", "{err}" }
+                },
+                None => rsx! {},
+            }
+            if let Some(link) = link() {
+                input { class: "scenario-link", readonly: true, value: "{link}" }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario() -> Scenario {
+        Scenario {
+            version: VERSION,
+            array: vec![1, 2, 3],
+            stack: vec![4, 5],
+            chaos: ChaosSettings {
+                mode: FaultMode::Off,
+                rate: 10,
+                seed: 7,
+            },
+            pipeline: PipelineConfig::default(),
+            stages: pipeline::default_stages(),
+            log: vec!["hello".to_string()],
+        }
+    }
+
+    #[test]
+    fn round_trips_through_json_and_links() {
+        let scenario = scenario();
+        assert_eq!(Scenario::from_json(&scenario.to_json()), Ok(scenario.clone()));
+        let link = scenario.share_link("https://example.com", "/demos/contiguous-array");
+        let fragment = link.split_once('#').unwrap().1;
+        assert_eq!(Scenario::from_fragment(fragment), Some(Ok(scenario)));
+        assert_eq!(Scenario::from_fragment("#top"), None);
+    }
+
+    #[test]
+    fn refuses_more_items_than_it_can_load() {
+        let mut scenario = scenario();
+        scenario.stack = vec![0; MAX_ITEMS + 1];
+        let err = Scenario::from_json(&scenario.to_json()).unwrap_err();
+        assert!(err.contains("the stack holds 4097 items"), "{err}");
+        scenario.stack = vec![0; MAX_ITEMS];
+        assert!(scenario.validate().is_ok());
+    }
+}
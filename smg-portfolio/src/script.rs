@@ -0,0 +1,1165 @@
+//! A small scenario language for scripting concurrent workloads.
+//!
+//! Scripts run against the `CONTIGUOUS_ARRAY` and `MAGIC_DATA` singletons
+//! through the same helpers the buttons use, so chaos, snapshot tracking and
+//! pop retries all apply. Statements need no separator; `;` and newlines
+//! are both optional, and `#` starts a comment.
+//!
+//! ```text
+//! on array | on stack          pick the structure the next statements use
+//! workers N                    how many sections `section` splits the array into
+//! seed A..B [step S]           empty the structure, push A*S .. (B-1)*S
+//! clear                        pop until empty
+//! push EXPR                    push a value
+//! pop                          pop one value (retried like the consumer)
+//! map SELECTOR OP...           apply OPs to every index in SELECTOR
+//! stats SELECTOR               scan SELECTOR and log sum/avg/min/max
+//! report                       merge every `stats` since the last report
+//! sleep N ms                   yield and publish the array
+//! log "text {len} {worker} {i} {section}"
+//! repeat N BODY                run BODY N times; `i` counts from 0
+//! worker[A..B] BODY            spawn one worker per id, without waiting
+//! join                         wait for every spawned worker (not inside one)
+//! require seeded               stop if the array is empty
+//!
+//! SELECTOR: section | all | middle | index ATOM
+//! OP:       + - * % = followed by ATOM, applied left to right
+//! EXPR:     ATOM (* ATOM)* (+ ATOM (* ATOM)*)*
+//! ATOM:     integer | worker | i | len | idx (idx only inside map)
+//! BODY:     one statement or { statements }
+//! ```
+//!
+//! A script joins its workers before it finishes, so `report` after the
+//! last `join` sees every section. Every statement, `repeat` iteration and
+//! value a `map`, `stats` or `seed` touches costs one step: a script yields
+//! to the page every `YIELD_STEPS` steps and stops after `MAX_STEPS`,
+//! however its `repeat`s are nested.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::ops::Range;
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use futures_util::future::{FutureExt, LocalBoxFuture};
+use tracing::{debug, info, info_span, Instrument};
+
+use crate::pop_errors::{PopOp, CONSUMER_RETRY, DRAIN_RETRY};
+use crate::snapshot::{ScanGuard, ScanVerdict, SectionStats};
+use crate::ui::{Button, Panel, SpecCard, Variant};
+use crate::{chaos, mutate_element, push_log, read_element, spawn_span, DemoEnv, Target, CONTIGUOUS_ARRAY};
+
+const MAX_REPEAT: i64 = 1_000;
+const MAX_WORKER_ID: i64 = 16;
+const MAX_SLEEP_MS: i64 = 10_000;
+const MAX_SEED: i64 = 4_096;
+/// Steps one run may take, its workers included.
+const MAX_STEPS: usize = 1_000_000;
+/// Steps between two yields, so a busy script cannot freeze the tab.
+const YIELD_STEPS: usize = 1_000;
+
+/// A script that ships with the page; the array buttons run these.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Builtin {
+    pub name: &'static str,
+    pub source: &'static str,
+}
+
+pub const SEED: Builtin = Builtin {
+    name: "SEED MATRIX",
+    source: include_str!("../scripts/seed.scn"),
+};
+
+pub const MATRIX_TRANSFORM: Builtin = Builtin {
+    name: "MATRIX TRANSFORM",
+    source: include_str!("../scripts/matrix_transform.scn"),
+};
+
+pub const PIPELINE: Builtin = Builtin {
+    name: "CONCURRENT PIPELINE",
+    source: include_str!("../scripts/pipeline.scn"),
+};
+
+pub const STATISTICS: Builtin = Builtin {
+    name: "CONCURRENT STATISTICS",
+    source: include_str!("../scripts/statistics.scn"),
+};
+
+pub const BUILTINS: [Builtin; 4] = [SEED, MATRIX_TRANSFORM, PIPELINE, STATISTICS];
+
+// ── Syntax ─────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub col: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.col, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Atom {
+    Int(i64),
+    Worker,
+    I,
+    Len,
+    Idx,
+}
+
+/// A sum of products of atoms.
+type Expr = Vec<Vec<Atom>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Rem,
+    Set,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Selector {
+    Section,
+    All,
+    Middle,
+    Index(Atom),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Stmt {
+    Workers(usize),
+    On(Target),
+    Seed { from: i64, to: i64, step: i64 },
+    Clear,
+    Push(Expr),
+    Pop,
+    Map { selector: Selector, ops: Vec<(Op, Atom)> },
+    Stats(Selector),
+    Report,
+    Sleep(u32),
+    Log(String),
+    Repeat { times: usize, body: Vec<Stmt> },
+    Worker { ids: Range<usize>, body: Rc<[Stmt]> },
+    Join,
+    RequireSeeded,
+}
+
+/// A parsed script, ready to `run`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Script {
+    stmts: Vec<Stmt>,
+}
+
+impl Script {
+    /// Top-level statements, not counting the bodies of blocks.
+    pub fn statement_count(&self) -> usize {
+        self.stmts.len()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Tok {
+    Word(String),
+    Int(i64),
+    Str(String),
+    Sym(char),
+    DotDot,
+    End,
+}
+
+impl fmt::Display for Tok {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tok::Word(word) => write!(f, "`{word}`"),
+            Tok::Int(n) => write!(f, "`{n}`"),
+            Tok::Str(_) => write!(f, "a string"),
+            Tok::Sym(c) => write!(f, "`{c}`"),
+            Tok::DotDot => write!(f, "`..`"),
+            Tok::End => write!(f, "the end of the script"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    tok: Tok,
+    line: usize,
+    col: usize,
+}
+
+fn lex(source: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    let (mut line, mut col) = (1, 1);
+    while let Some(&c) = chars.peek() {
+        let (start_line, start_col) = (line, col);
+        let error = |message: String| ParseError {
+            line: start_line,
+            col: start_col,
+            message,
+        };
+        let mut bump = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+            let c = chars.next();
+            if c == Some('\n') {
+                line += 1;
+                col = 1;
+            } else {
+                col += 1;
+            }
+            c
+        };
+        let tok = match c {
+            c if c.is_whitespace() => {
+                bump(&mut chars);
+                continue;
+            }
+            '#' => {
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    bump(&mut chars);
+                }
+                continue;
+            }
+            '0'..='9' => {
+                let mut digits = String::new();
+                while let Some(d) = chars.peek().copied().filter(char::is_ascii_digit) {
+                    digits.push(d);
+                    bump(&mut chars);
+                }
+                Tok::Int(digits.parse().map_err(|_| error(format!("number `{digits}` is too large")))?)
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut word = String::new();
+                while let Some(w) = chars.peek().copied().filter(|w| w.is_ascii_alphanumeric() || *w == '_') {
+                    word.push(w);
+                    bump(&mut chars);
+                }
+                Tok::Word(word)
+            }
+            '"' => {
+                bump(&mut chars);
+                let mut text = String::new();
+                loop {
+                    match bump(&mut chars) {
+                        Some('"') => break,
+                        Some('\\') => match bump(&mut chars) {
+                            Some(escaped @ ('"' | '\\')) => text.push(escaped),
+                            _ => return Err(error("only \\\" and \\\\ can be escaped".to_string())),
+                        },
+                        Some('\n') | None => return Err(error("string is missing its closing `\"`".to_string())),
+                        Some(other) => text.push(other),
+                    }
+                }
+                Tok::Str(text)
+            }
+            '.' => {
+                bump(&mut chars);
+                if bump(&mut chars) != Some('.') {
+                    return Err(error("expected `..`".to_string()));
+                }
+                Tok::DotDot
+            }
+            ';' | '{' | '}' | '[' | ']' | '+' | '-' | '*' | '%' | '=' => {
+                bump(&mut chars);
+                Tok::Sym(c)
+            }
+            other => return Err(error(format!("unexpected character `{other}`"))),
+        };
+        tokens.push(Token {
+            tok,
+            line: start_line,
+            col: start_col,
+        });
+    }
+    tokens.push(Token { tok: Tok::End, line, col });
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// `worker` bodies the parser is inside of.
+    worker_depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos]
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.pos].clone();
+        if token.tok != Tok::End {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn error_at(token: &Token, message: String) -> ParseError {
+        ParseError {
+            line: token.line,
+            col: token.col,
+            message,
+        }
+    }
+
+    fn expected(&mut self, what: &str) -> ParseError {
+        let token = self.peek().clone();
+        Self::error_at(&token, format!("expected {what}, found {}", token.tok))
+    }
+
+    fn eat_sym(&mut self, c: char) -> bool {
+        if self.peek().tok == Tok::Sym(c) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_sym(&mut self, c: char) -> Result<(), ParseError> {
+        if self.eat_sym(c) {
+            Ok(())
+        } else {
+            Err(self.expected(&format!("`{c}`")))
+        }
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        if matches!(&self.peek().tok, Tok::Word(w) if w == word) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_word(&mut self, word: &str) -> Result<(), ParseError> {
+        if self.eat_word(word) {
+            Ok(())
+        } else {
+            Err(self.expected(&format!("`{word}`")))
+        }
+    }
+
+    fn int(&mut self, what: &str, min: i64, max: i64) -> Result<i64, ParseError> {
+        let token = self.peek().clone();
+        match token.tok {
+            Tok::Int(n) if (min..=max).contains(&n) => {
+                self.next();
+                Ok(n)
+            }
+            Tok::Int(n) => Err(Self::error_at(&token, format!("{what} must be between {min} and {max}, got {n}"))),
+            _ => Err(self.expected(what)),
+        }
+    }
+
+    fn program(&mut self) -> Result<Vec<Stmt>, ParseError> {
+        let mut stmts = Vec::new();
+        while self.peek().tok != Tok::End {
+            if self.peek().tok == Tok::Sym('}') {
+                return Err(self.expected("a statement"));
+            }
+            stmts.extend(self.stmt()?);
+        }
+        Ok(stmts)
+    }
+
+    /// One statement, or `None` for a bare `;`.
+    fn stmt(&mut self) -> Result<Option<Stmt>, ParseError> {
+        if self.eat_sym(';') {
+            return Ok(None);
+        }
+        let token = self.next();
+        let Tok::Word(keyword) = &token.tok else {
+            return Err(Self::error_at(&token, format!("expected a statement, found {}", token.tok)));
+        };
+        let stmt = match keyword.as_str() {
+            "workers" => Stmt::Workers(self.int("a worker count", 1, MAX_WORKER_ID)? as usize),
+            "on" => {
+                if self.eat_word("array") {
+                    Stmt::On(Target::Array)
+                } else if self.eat_word("stack") {
+                    Stmt::On(Target::Stack)
+                } else {
+                    return Err(self.expected("`array` or `stack`"));
+                }
+            }
+            "seed" => {
+                let from = self.int("the first seed index", 0, MAX_SEED)?;
+                if self.next().tok != Tok::DotDot {
+                    return Err(Self::error_at(&token, "seed needs a range like `0..32`".to_string()));
+                }
+                let to = self.int("the end of the seed range", from, MAX_SEED)?;
+                let step = if self.eat_word("step") {
+                    self.int("a step", 1, 1_000)?
+                } else {
+                    1
+                };
+                Stmt::Seed { from, to, step }
+            }
+            "clear" => Stmt::Clear,
+            "push" => Stmt::Push(self.expr()?),
+            "pop" => Stmt::Pop,
+            "map" => {
+                let selector = self.selector()?;
+                let mut ops = Vec::new();
+                loop {
+                    let op = match self.peek().tok {
+                        Tok::Sym('+') => Op::Add,
+                        Tok::Sym('-') => Op::Sub,
+                        Tok::Sym('*') => Op::Mul,
+                        Tok::Sym('%') => Op::Rem,
+                        Tok::Sym('=') => Op::Set,
+                        _ => break,
+                    };
+                    self.next();
+                    let at = self.peek().clone();
+                    let atom = self.atom(true)?;
+                    if op == Op::Rem && atom == Atom::Int(0) {
+                        return Err(Self::error_at(&at, "`% 0` would divide by zero".to_string()));
+                    }
+                    ops.push((op, atom));
+                }
+                if ops.is_empty() {
+                    return Err(self.expected("an operation like `+1` or `%100`"));
+                }
+                Stmt::Map { selector, ops }
+            }
+            "stats" => Stmt::Stats(self.selector()?),
+            "report" => Stmt::Report,
+            "sleep" => {
+                let ms = self.int("a duration", 0, MAX_SLEEP_MS)? as u32;
+                self.expect_word("ms")?;
+                Stmt::Sleep(ms)
+            }
+            "log" => match self.next().tok {
+                Tok::Str(text) => Stmt::Log(text),
+                _ => return Err(Self::error_at(&token, "log needs a quoted message".to_string())),
+            },
+            "repeat" => {
+                let times = self.int("a repeat count", 0, MAX_REPEAT)? as usize;
+                Stmt::Repeat {
+                    times,
+                    body: self.body()?,
+                }
+            }
+            "worker" => {
+                self.expect_sym('[')?;
+                let first = self.int("a worker id", 0, MAX_WORKER_ID - 1)?;
+                let end = if self.peek().tok == Tok::DotDot {
+                    self.next();
+                    self.int("the end of the worker range", first + 1, MAX_WORKER_ID)?
+                } else {
+                    first + 1
+                };
+                self.expect_sym(']')?;
+                self.worker_depth += 1;
+                let body = self.body();
+                self.worker_depth -= 1;
+                Stmt::Worker {
+                    ids: first as usize..end as usize,
+                    body: body?.into(),
+                }
+            }
+            // The worker counts itself as running, so it would wait forever.
+            "join" if self.worker_depth > 0 => {
+                return Err(Self::error_at(&token, "`join` inside a worker would wait for itself".to_string()))
+            }
+            "join" => Stmt::Join,
+            "require" => {
+                self.expect_word("seeded")?;
+                Stmt::RequireSeeded
+            }
+            other => return Err(Self::error_at(&token, format!("unknown statement `{other}`"))),
+        };
+        Ok(Some(stmt))
+    }
+
+    /// `{ statements }` or a single statement.
+    fn body(&mut self) -> Result<Vec<Stmt>, ParseError> {
+        if !self.eat_sym('{') {
+            return match self.stmt()? {
+                Some(stmt) => Ok(vec![stmt]),
+                None => Err(self.expected("a statement or `{`")),
+            };
+        }
+        let open = self.tokens[self.pos - 1].clone();
+        let mut stmts = Vec::new();
+        loop {
+            match self.peek().tok {
+                Tok::Sym('}') => {
+                    self.next();
+                    return Ok(stmts);
+                }
+                Tok::End => return Err(Self::error_at(&open, "this `{` is never closed".to_string())),
+                _ => stmts.extend(self.stmt()?),
+            }
+        }
+    }
+
+    fn selector(&mut self) -> Result<Selector, ParseError> {
+        if self.eat_word("section") {
+            Ok(Selector::Section)
+        } else if self.eat_word("all") {
+            Ok(Selector::All)
+        } else if self.eat_word("middle") {
+            Ok(Selector::Middle)
+        } else if self.eat_word("index") {
+            Ok(Selector::Index(self.atom(false)?))
+        } else {
+            Err(self.expected("`section`, `all`, `middle` or `index N`"))
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        let mut sum = Vec::new();
+        loop {
+            let mut product = vec![self.atom(false)?];
+            while self.eat_sym('*') {
+                product.push(self.atom(false)?);
+            }
+            sum.push(product);
+            if !self.eat_sym('+') {
+                return Ok(sum);
+            }
+        }
+    }
+
+    fn atom(&mut self, allow_idx: bool) -> Result<Atom, ParseError> {
+        let token = self.peek().clone();
+        let atom = match &token.tok {
+            Tok::Int(n) => Atom::Int(*n),
+            Tok::Word(word) if word == "worker" => Atom::Worker,
+            Tok::Word(word) if word == "i" => Atom::I,
+            Tok::Word(word) if word == "len" => Atom::Len,
+            Tok::Word(word) if word == "idx" && allow_idx => Atom::Idx,
+            Tok::Word(word) if word == "idx" => {
+                return Err(Self::error_at(&token, "`idx` is only defined inside a map operation".to_string()))
+            }
+            _ => return Err(self.expected("a number, `worker`, `i` or `len`")),
+        };
+        self.next();
+        Ok(atom)
+    }
+}
+
+pub fn parse(source: &str) -> Result<Script, ParseError> {
+    let mut parser = Parser {
+        tokens: lex(source)?,
+        pos: 0,
+        worker_depth: 0,
+    };
+    Ok(Script {
+        stmts: parser.program()?,
+    })
+}
+
+// ── Execution ──────────────────────────────────────────────────────────
+
+/// State shared by a script and every worker it spawns.
+#[derive(Default)]
+struct Shared {
+    running_workers: Cell<usize>,
+    steps: Cell<usize>,
+    sections: RefCell<Vec<(SectionStats, ScanVerdict)>>,
+    /// Covers every section since the last `report`.
+    run_guard: RefCell<Option<ScanGuard>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Vars {
+    worker: usize,
+    i: usize,
+    len: usize,
+}
+
+impl Vars {
+    fn eval(self, atom: Atom, idx: usize) -> i64 {
+        match atom {
+            Atom::Int(n) => n,
+            Atom::Worker => self.worker as i64,
+            Atom::I => self.i as i64,
+            Atom::Len => self.len as i64,
+            Atom::Idx => idx as i64,
+        }
+    }
+
+    fn eval_expr(self, expr: &Expr) -> i64 {
+        expr.iter()
+            .map(|product| product.iter().fold(1i64, |acc, atom| acc.wrapping_mul(self.eval(*atom, 0))))
+            .fold(0i64, i64::wrapping_add)
+    }
+}
+
+fn apply(ops: &[(Op, Atom)], value: i32, vars: Vars, idx: usize) -> i32 {
+    ops.iter().fold(value as i64, |acc, (op, atom)| {
+        let operand = vars.eval(*atom, idx);
+        match op {
+            Op::Add => acc.wrapping_add(operand),
+            Op::Sub => acc.wrapping_sub(operand),
+            Op::Mul => acc.wrapping_mul(operand),
+            Op::Rem => acc.checked_rem(operand).unwrap_or(acc),
+            Op::Set => operand,
+        }
+    }) as i32
+}
+
+#[derive(Clone)]
+struct Ctx {
+    name: Rc<str>,
+    env: DemoEnv,
+    target: Target,
+    workers: usize,
+    worker: usize,
+    i: usize,
+    shared: Rc<Shared>,
+}
+
+impl Ctx {
+    fn len(&self) -> usize {
+        self.env.len(self.target)
+    }
+
+    fn vars(&self) -> Vars {
+        Vars {
+            worker: self.worker,
+            i: self.i,
+            len: self.len(),
+        }
+    }
+
+    fn section(&self, len: usize) -> Result<Range<usize>, String> {
+        if self.worker >= self.workers {
+            return Err(format!(
+                "worker {} has no section with `workers {}`",
+                self.worker, self.workers
+            ));
+        }
+        let size = len / self.workers;
+        let start = self.worker * size;
+        let end = if self.worker == self.workers - 1 { len } else { start + size };
+        Ok(start..end)
+    }
+
+    fn range(&self, selector: Selector) -> Result<Range<usize>, String> {
+        let len = CONTIGUOUS_ARRAY.len();
+        Ok(match selector {
+            Selector::Section => self.section(len)?,
+            Selector::All => 0..len,
+            Selector::Middle if len == 0 => 0..0,
+            Selector::Middle => len / 2..len / 2 + 1,
+            Selector::Index(atom) => index_range(self.vars().eval(atom, 0))?,
+        })
+    }
+
+    /// Charges `cost` steps to the run, yielding every `YIELD_STEPS`.
+    async fn step(&self, cost: usize) -> Result<(), String> {
+        if charge(&self.shared.steps, cost)? {
+            self.publish();
+            gloo_timers::future::TimeoutFuture::new(0).await;
+        }
+        Ok(())
+    }
+
+    fn require_array(&self, what: &str) -> Result<(), String> {
+        match self.target {
+            Target::Array => Ok(()),
+            Target::Stack => Err(format!("`{what}` needs index access; use `on array`")),
+        }
+    }
+
+    fn interpolate(&self, text: &str) -> String {
+        let section = self.section(CONTIGUOUS_ARRAY.len()).map_or(0, |range| range.len());
+        text.replace("{len}", &self.len().to_string())
+            .replace("{worker}", &self.worker.to_string())
+            .replace("{i}", &self.i.to_string())
+            .replace("{section}", &section.to_string())
+    }
+
+    fn publish(&self) {
+        self.env.publish(self.target);
+    }
+
+    fn push(&self, value: i32) -> Result<(), String> {
+        self.env.push(self.target, value)?;
+        debug!(value, "script push");
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), String> {
+        let op = match self.target {
+            Target::Array => PopOp::ArraySeedDrain,
+            Target::Stack => PopOp::StackDrain,
+        };
+        while self.env.pop(self.target, op, DRAIN_RETRY).await?.is_some() {}
+        Ok(())
+    }
+
+    async fn stats(&self, selector: Selector) -> Result<(), String> {
+        self.require_array("stats")?;
+        let range = self.range(selector)?;
+        self.shared
+            .run_guard
+            .borrow_mut()
+            .get_or_insert_with(|| ScanGuard::begin(0..CONTIGUOUS_ARRAY.len()));
+        let guard = ScanGuard::begin(range.clone());
+        let mut stats = SectionStats::default();
+        for idx in range.clone() {
+            stats.add(read_element(idx));
+            if idx % 4 == 0 {
+                gloo_timers::future::TimeoutFuture::new(5).await;
+            }
+        }
+        let verdict = guard.finish();
+        debug!(%verdict, "section scanned");
+        push_log(
+            self.env.race_log,
+            format!(
+                "Section {} [{}..{}]: sum={}, avg={}, min={}, max={} · {}",
+                self.worker,
+                range.start,
+                range.end.saturating_sub(1),
+                stats.sum,
+                stats.avg(),
+                stats.min,
+                stats.max,
+                verdict,
+            ),
+        );
+        self.publish();
+        self.shared.sections.borrow_mut().push((stats, verdict));
+        Ok(())
+    }
+
+    fn report(&self) {
+        let sections = std::mem::take(&mut *self.shared.sections.borrow_mut());
+        let run_verdict = self
+            .shared
+            .run_guard
+            .borrow_mut()
+            .take()
+            .map_or(ScanVerdict::Consistent, ScanGuard::finish);
+        let mut global = SectionStats::default();
+        for (stats, _) in &sections {
+            global.merge(stats);
+        }
+        let torn = sections.iter().filter(|(_, verdict)| !verdict.is_consistent()).count();
+        info!(torn, %run_verdict, "statistics joined");
+        push_log(
+            self.env.race_log,
+            format!(
+                "GLOBAL: sum={}, avg={}, min={}, max={} · {} of {} sections torn; snapshot {}",
+                global.sum,
+                global.avg(),
+                global.min,
+                global.max,
+                torn,
+                sections.len(),
+                run_verdict,
+            ),
+        );
+    }
+
+    async fn join(&self) {
+        while self.shared.running_workers.get() > 0 {
+            gloo_timers::future::TimeoutFuture::new(5).await;
+        }
+    }
+
+    fn spawn_worker(&self, worker: usize, body: Rc<[Stmt]>) {
+        let mut child = self.clone();
+        child.worker = worker;
+        child.i = 0;
+        self.shared.running_workers.set(self.shared.running_workers.get() + 1);
+        let span = info_span!("script_worker", script = %self.name, worker);
+        spawn(
+            async move {
+                if let Err(err) = exec_block(&mut child, &body).await {
+                    push_log(child.env.race_log, format!("{} worker {}: {}", child.name, worker, err));
+                }
+                child.publish();
+                child.shared.running_workers.set(child.shared.running_workers.get() - 1);
+            }
+            .instrument(spawn_span(span)),
+        );
+    }
+}
+
+fn exec_block<'a>(ctx: &'a mut Ctx, stmts: &'a [Stmt]) -> LocalBoxFuture<'a, Result<(), String>> {
+    async move {
+        for stmt in stmts {
+            exec(ctx, stmt).await?;
+        }
+        Ok(())
+    }
+    .boxed_local()
+}
+
+/// Adds `cost` to `steps`. True when the total just crossed a multiple of
+/// `YIELD_STEPS`; an error once it passes `MAX_STEPS`.
+fn charge(steps: &Cell<usize>, cost: usize) -> Result<bool, String> {
+    let before = steps.get();
+    let after = before.saturating_add(cost);
+    steps.set(after);
+    if after > MAX_STEPS {
+        return Err(format!("stopped after {MAX_STEPS} steps"));
+    }
+    Ok(before / YIELD_STEPS != after / YIELD_STEPS)
+}
+
+/// `index N` as a range, or an error when `N` is no index on this target.
+fn index_range(value: i64) -> Result<Range<usize>, String> {
+    let idx = usize::try_from(value).map_err(|_| format!("index {value} is out of range"))?;
+    let end = idx.checked_add(1).ok_or_else(|| format!("index {value} is out of range"))?;
+    Ok(idx..end)
+}
+
+async fn exec(ctx: &mut Ctx, stmt: &Stmt) -> Result<(), String> {
+    ctx.step(1).await?;
+    match stmt {
+        Stmt::Workers(n) => ctx.workers = *n,
+        Stmt::On(target) => ctx.target = *target,
+        Stmt::Seed { from, to, step } => {
+            ctx.clear().await.map_err(|err| format!("could not clear the matrix ({err})"))?;
+            ctx.step((to - from) as usize).await?;
+            for k in *from..*to {
+                ctx.push((k * step) as i32)?;
+            }
+            if ctx.target == Target::Array {
+                chaos::clear_torn();
+            }
+            debug!(len = ctx.len(), "seeded");
+            ctx.publish();
+        }
+        Stmt::Clear => {
+            ctx.clear().await?;
+            ctx.publish();
+        }
+        Stmt::Push(expr) => ctx.push(ctx.vars().eval_expr(expr) as i32)?,
+        Stmt::Pop => {
+            let op = match ctx.target {
+                Target::Array => PopOp::ArrayScriptPop,
+                Target::Stack => PopOp::StackScriptPop,
+            };
+            // A failed pop is reported and the script carries on, like the
+            // consumer it replaces.
+            match ctx.env.pop(ctx.target, op, CONSUMER_RETRY).await {
+                Ok(popped) => debug!(?popped, "script pop"),
+                Err(err) => push_log(ctx.env.race_log, format!("{}: {}", ctx.name, err)),
+            }
+        }
+        Stmt::Map { selector, ops } => {
+            ctx.require_array("map")?;
+            let vars = ctx.vars();
+            let range = ctx.range(*selector)?;
+            ctx.step(range.len()).await?;
+            for idx in range {
+                mutate_element(idx, |v| *v = apply(ops, *v, vars, idx));
+            }
+        }
+        Stmt::Stats(selector) => {
+            ctx.step(ctx.range(*selector)?.len()).await?;
+            ctx.stats(*selector).await?
+        }
+        Stmt::Report => ctx.report(),
+        Stmt::Sleep(ms) => {
+            ctx.publish();
+            gloo_timers::future::TimeoutFuture::new(*ms).await;
+        }
+        Stmt::Log(text) => push_log(ctx.env.race_log, ctx.interpolate(text)),
+        Stmt::Repeat { times, body } => {
+            let outer = ctx.i;
+            for i in 0..*times {
+                // An empty body still costs its iterations.
+                ctx.step(1).await?;
+                ctx.i = i;
+                exec_block(ctx, body).await?;
+            }
+            ctx.i = outer;
+        }
+        Stmt::Worker { ids, body } => {
+            for worker in ids.clone() {
+                ctx.spawn_worker(worker, body.clone());
+            }
+        }
+        Stmt::Join => ctx.join().await,
+        Stmt::RequireSeeded => {
+            if CONTIGUOUS_ARRAY.len() == 0 {
+                return Err("SEED first, the matrix is empty".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Runs `script` in the background, joining its workers at the end.
+pub fn run(name: &str, script: Script, env: DemoEnv) {
+    let mut ctx = Ctx {
+        name: name.into(),
+        env,
+        target: Target::Array,
+        workers: 4,
+        worker: 0,
+        i: 0,
+        shared: Rc::default(),
+    };
+    let span = info_span!("script", name);
+    spawn(
+        async move {
+            let result = exec_block(&mut ctx, &script.stmts).await;
+            ctx.join().await;
+            match result {
+                Ok(()) => debug!("script finished"),
+                Err(err) => push_log(env.race_log, format!("{} stopped: {}", ctx.name, err)),
+            }
+            ctx.publish();
+        }
+        .instrument(spawn_span(span)),
+    );
+}
+
+/// Parses and runs a built-in script.
+pub fn run_builtin(builtin: Builtin, env: DemoEnv) {
+    match parse(builtin.source) {
+        Ok(script) => run(builtin.name, script, env),
+        Err(err) => push_log(env.race_log, format!("{}: {}", builtin.name, err)),
+    }
+}
+
+/// Edit and run scenario scripts; the array buttons run the built-ins.
+#[component]
+pub fn ScriptEditor(env: DemoEnv) -> Element {
+    let mut source = use_signal(|| MATRIX_TRANSFORM.source.to_string());
+    let parsed = parse(&source.read());
+
+    rsx! {
+        Panel { id: "script-area",
+            SpecCard { label: "SCRIPT:", value: "scenario DSL, parsed in Rust",
+                "Every button above runs one of these scripts. Load one, change it, or write your own; "
+                "it runs against the same ContiguousArray and LinkedStack, and its output goes to the Live Operation Log."
+            }
+
+            div { class: "trace-toolbar",
+                label {
+                    "Load built-in "
+                    select {
+                        onchange: move |evt| {
+                            if let Some(builtin) = BUILTINS.into_iter().find(|b| b.name == evt.value()) {
+                                source.set(builtin.source.to_string());
+                            }
+                        },
+                        for builtin in BUILTINS {
+                            option {
+                                value: "{builtin.name}",
+                                selected: *source.read() == builtin.source,
+                                "{builtin.name}"
+                            }
+                        }
+                    }
+                }
+                Button {
+                    variant: Variant::Burst,
+                    disabled: parsed.is_err(),
+                    onclick: move |_| {
+                        if let Ok(script) = parse(&source.read()) {
+                            info!(statements = script.statement_count(), "user script started");
+                            run("script", script, env);
+                        }
+                    },
+                    "RUN SCRIPT"
+                }
+            }
+
+            textarea {
+                class: "script-editor",
+                rows: "16",
+                spellcheck: "false",
+                value: "{source}",
+                oninput: move |evt| source.set(evt.value()),
+            }
+
+            match &parsed {
+                Ok(script) => rsx! {
+                    div { class: "script-status outcome-hit",
+                        "parsed: {script.statement_count()} top-level statement(s)"
+                    }
+                },
+                Err(err) => {
+                    let line = source.read().lines().nth(err.line - 1).unwrap_or_default().to_string();
+                    let caret = format!("{}^", " ".repeat(err.col.saturating_sub(1)));
+                    rsx! {
+                        div { class: "script-status outcome-different", "parse error at {err}" }
+                        pre { class: "script-error", "{line}\n{caret}" }
+                    }
+                }
+            }
+
+            div { class: "demo-explanation",
+                h4 { "Statements" }
+                ul {
+                    li {
+                        strong { "STRUCTURE: " }
+                        "on array | on stack, seed A..B step S, clear, push EXPR, pop, require seeded"
+                    }
+                    li {
+                        strong { "ARRAY: " }
+                        "map SELECTOR OPS (e.g. map section +7 %100), stats SELECTOR, report. "
+                        "Selectors are section, all, middle and index N; ops are + - * % = and may use idx."
+                    }
+                    li {
+                        strong { "CONCURRENCY: " }
+                        "workers N sets how many sections there are; worker[0..4] {{ ... }} spawns one worker per id "
+                        "without waiting; join waits for all of them."
+                    }
+                    li {
+                        strong { "FLOW: " }
+                        "repeat N {{ ... }}, sleep N ms, log \"text\". Inside text and expressions, "
+                        "len, worker, i (the repeat counter) and section are available."
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toks(source: &str) -> Vec<Tok> {
+        lex(source).unwrap().into_iter().map(|token| token.tok).collect()
+    }
+
+    fn parse_err(source: &str) -> ParseError {
+        parse(source).expect_err("script should not parse")
+    }
+
+    #[test]
+    fn lexes_words_numbers_strings_and_symbols() {
+        assert_eq!(
+            toks("seed 0..32 # comment\nlog \"a \\\"b\\\"\" map %"),
+            vec![
+                Tok::Word("seed".into()),
+                Tok::Int(0),
+                Tok::DotDot,
+                Tok::Int(32),
+                Tok::Word("log".into()),
+                Tok::Str("a \"b\"".into()),
+                Tok::Word("map".into()),
+                Tok::Sym('%'),
+                Tok::End,
+            ]
+        );
+    }
+
+    #[test]
+    fn lex_errors_carry_their_position() {
+        let err = lex("push 1\n  log \"open").unwrap_err();
+        assert_eq!((err.line, err.col), (2, 7));
+        assert!(err.message.contains("closing"));
+        assert!(lex("push 1.5").unwrap_err().message.contains("`..`"));
+        assert!(lex("push @").unwrap_err().message.contains("`@`"));
+        assert!(lex("push 99999999999999999999").unwrap_err().message.contains("too large"));
+    }
+
+    #[test]
+    fn parses_nested_blocks() {
+        let script = parse("workers 2; worker[0..2] { repeat 3 map section +1 %7 } join report").unwrap();
+        assert_eq!(script.statement_count(), 4);
+        let Stmt::Worker { ids, body } = &script.stmts[1] else {
+            panic!("expected a worker, got {:?}", script.stmts[1]);
+        };
+        assert_eq!(*ids, 0..2);
+        assert_eq!(
+            body[..],
+            [Stmt::Repeat {
+                times: 3,
+                body: vec![Stmt::Map {
+                    selector: Selector::Section,
+                    ops: vec![(Op::Add, Atom::Int(1)), (Op::Rem, Atom::Int(7))],
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert!(parse_err("jump").message.contains("unknown statement `jump`"));
+        assert!(parse_err("workers 0").message.contains("between 1 and 16"));
+        assert!(parse_err("repeat 1001 push 1").message.contains("between 0 and 1000"));
+        assert!(parse_err("map all % 0").message.contains("divide by zero"));
+        assert!(parse_err("push idx").message.contains("only defined inside a map"));
+        assert!(parse_err("sleep 5").message.contains("`ms`"));
+        let err = parse_err("repeat 2 {\n push 1");
+        assert_eq!((err.line, err.col), (1, 10));
+        assert!(err.message.contains("never closed"));
+        assert!(parse_err("}").message.contains("expected a statement"));
+    }
+
+    #[test]
+    fn rejects_join_inside_a_worker() {
+        let err = parse_err("worker[0] {\n  push 1\n  join\n}");
+        assert_eq!((err.line, err.col), (3, 3));
+        assert!(err.message.contains("wait for itself"));
+        assert!(parse_err("worker[0..2] repeat 2 join").message.contains("wait for itself"));
+        // Still fine once the worker body is closed.
+        assert!(parse("worker[0] { push 1 } join").is_ok());
+    }
+
+    #[test]
+    fn every_builtin_parses() {
+        for builtin in BUILTINS {
+            let script = parse(builtin.source).unwrap_or_else(|err| panic!("{}: {err}", builtin.name));
+            assert!(script.statement_count() > 0, "{} is empty", builtin.name);
+        }
+    }
+
+    #[test]
+    fn expressions_and_ops_wrap() {
+        let vars = Vars { worker: 2, i: 3, len: 10 };
+        let expr = parse("push worker*10 + i").unwrap();
+        let Stmt::Push(expr) = &expr.stmts[0] else { unreachable!() };
+        assert_eq!(vars.eval_expr(expr), 23);
+        assert_eq!(apply(&[(Op::Add, Atom::Int(1))], i32::MAX, vars, 0), i32::MIN);
+        assert_eq!(apply(&[(Op::Mul, Atom::Len), (Op::Rem, Atom::Idx)], 7, vars, 4), 2);
+        assert_eq!(apply(&[(Op::Rem, Atom::Idx)], 7, vars, 0), 7);
+        assert_eq!(apply(&[(Op::Set, Atom::Worker), (Op::Sub, Atom::I)], 99, vars, 0), -1);
+    }
+
+    #[test]
+    fn index_selector_rejects_values_that_are_no_index() {
+        assert_eq!(index_range(3), Ok(3..4));
+        assert!(index_range(-1).is_err());
+        // Past `usize` on wasm32; on 64-bit targets a valid, if absent, index.
+        assert_eq!(index_range(i64::MAX).is_ok(), usize::try_from(i64::MAX).is_ok_and(|idx| idx < usize::MAX));
+    }
+
+    #[test]
+    fn step_budget_spans_nesting_and_yields() {
+        let steps = Cell::new(0);
+        assert_eq!(charge(&steps, YIELD_STEPS - 1), Ok(false));
+        assert_eq!(charge(&steps, 1), Ok(true));
+        assert_eq!(charge(&steps, 1), Ok(false));
+        // Three nested `repeat 1000` charge 1000 steps per middle iteration and
+        // run out of budget long before the 1000^2 of them finish.
+        let steps = Cell::new(0);
+        let mut stopped = None;
+        for outer in 0..1_000 * 1_000 {
+            if let Err(err) = charge(&steps, 1_000) {
+                stopped = Some((outer, err));
+                break;
+            }
+        }
+        let (outer, err) = stopped.expect("the budget never ran out");
+        assert_eq!(outer, MAX_STEPS / 1_000);
+        assert!(err.contains("steps"));
+    }
+}
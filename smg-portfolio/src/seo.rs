@@ -0,0 +1,113 @@
+//! Per-page `<head>` metadata: title, description, canonical URL, Open Graph
+//! and Twitter cards, and a JSON-LD `Person` built from the resume.
+//!
+//! Every route renders one `PageMeta`. Absolute URLs come from `site::SITE`;
+//! without it a page still gets its title and description. The card image is
+//! `assets/og-card.png`, which `discovery` writes next to `index.html` so its
+//! URL carries no asset hash.
+//!
+//! Link previews and crawlers read the pre-rendered HTML, which has exactly
+//! one set per page. In the browser Dioxus keeps the title in step with
+//! navigation but never removes a meta tag it has added.
+
+use dioxus::prelude::*;
+use serde_json::{json, Map, Value};
+
+use crate::resume::{Resume, RESUME};
+use crate::routes::Route;
+use crate::site::{Site, CARD_FILE, SITE};
+
+const CARD_WIDTH: &str = "1200";
+const CARD_HEIGHT: &str = "630";
+
+/// Head tags for the page at `route`. `title` goes before the site title,
+/// empty leaves the site title alone; an empty `description` falls back to
+/// the site's.
+#[component]
+pub fn PageMeta(
+    #[props(default)] title: String,
+    #[props(default)] description: String,
+    route: Route,
+    /// `YYYY-MM-DD` for a post, which makes the page an Open Graph article.
+    #[props(default)]
+    published: Option<String>,
+) -> Element {
+    let site = SITE.as_ref().ok();
+    let site_title = site.map_or("smg-portfolio", |site| site.title.as_str());
+    let full_title = if title.is_empty() { site_title.to_string() } else { format!("{title} · {site_title}") };
+    let description = match site {
+        Some(site) if description.is_empty() => site.description.clone(),
+        _ => description,
+    };
+    let kind = if published.is_some() { "article" } else { "website" };
+
+    rsx! {
+        document::Title { "{full_title}" }
+        document::Meta { name: "description", content: description.clone() }
+        if let Some(site) = site {
+            document::Link { rel: "canonical", href: site.absolute(&route.to_string()) }
+            document::Meta { property: "og:type", content: kind }
+            document::Meta { property: "og:site_name", content: site.title.clone() }
+            document::Meta { property: "og:title", content: full_title.clone() }
+            document::Meta { property: "og:description", content: description.clone() }
+            document::Meta { property: "og:url", content: site.absolute(&route.to_string()) }
+            document::Meta { property: "og:image", content: card_url(site) }
+            document::Meta { property: "og:image:width", content: CARD_WIDTH }
+            document::Meta { property: "og:image:height", content: CARD_HEIGHT }
+            if let Some(date) = published {
+                document::Meta { property: "article:published_time", content: date }
+            }
+            document::Meta { name: "twitter:card", content: "summary_large_image" }
+            document::Meta { name: "twitter:title", content: full_title.clone() }
+            document::Meta { name: "twitter:description", content: description.clone() }
+            document::Meta { name: "twitter:image", content: card_url(site) }
+        }
+    }
+}
+
+/// A schema.org `Person` for the resume's owner, on the profile and resume
+/// pages. Renders nothing if the resume or the site config failed to load.
+#[component]
+pub fn PersonSchema() -> Element {
+    let (Ok(resume), Ok(site)) = (&*RESUME, &*SITE) else {
+        return rsx! {};
+    };
+    // `</script>` inside a string would end the tag early.
+    let json = person(resume, site).to_string().replace('<', "\\u003c");
+
+    rsx! {
+        document::Script { r#type: "application/ld+json", "{json}" }
+    }
+}
+
+fn card_url(site: &Site) -> String {
+    site.absolute(&format!("/{CARD_FILE}"))
+}
+
+fn person(resume: &Resume, site: &Site) -> Value {
+    let basics = &resume.basics;
+    let mut person = Map::new();
+    person.insert("@context".into(), json!("https://schema.org"));
+    person.insert("@type".into(), json!("Person"));
+    person.insert("name".into(), json!(basics.name));
+    person.insert("jobTitle".into(), json!(basics.headline));
+    person.insert("address".into(), json!({ "@type": "PostalAddress", "addressLocality": basics.location }));
+    person.insert("url".into(), json!(site.url));
+    person.insert("image".into(), json!(card_url(site)));
+    if let Some(summary) = &basics.summary {
+        person.insert("description".into(), json!(summary));
+    }
+    if let Some(email) = &basics.email {
+        person.insert("email".into(), json!(format!("mailto:{email}")));
+    }
+    let same_as: Vec<&str> = resume.links.iter().map(|link| link.url.as_str()).collect();
+    if !same_as.is_empty() {
+        person.insert("sameAs".into(), json!(same_as));
+    }
+    let knows_about: Vec<&str> =
+        resume.skills.iter().flat_map(|group| &group.keywords).map(String::as_str).collect();
+    if !knows_about.is_empty() {
+        person.insert("knowsAbout".into(), json!(knows_about));
+    }
+    Value::Object(person)
+}
@@ -0,0 +1,76 @@
+//! Site-wide settings from `assets/site.toml`.
+//!
+//! The router only knows paths relative to `base_path`; anything that needs
+//! absolute URLs (the feed, the sitemap, share links in search results)
+//! joins them onto `url` here.
+
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+
+use crate::scenario::BASE_PATH;
+
+const SOURCE_PATH: &str = "assets/site.toml";
+const SOURCE: &str = include_str!("../assets/site.toml");
+/// The Atom feed, next to `index.html`; see `discovery`.
+pub const FEED_FILE: &str = "feed.xml";
+/// The Open Graph card image, next to `index.html`; see `seo`.
+pub const CARD_FILE: &str = "og-card.png";
+
+pub static SITE: LazyLock<Result<Site, String>> = LazyLock::new(|| {
+    let parsed = parse(SOURCE).map_err(|err| format!("{SOURCE_PATH}: {err}"));
+    if let Err(err) = &parsed {
+        tracing::error!(%err, "site config rejected");
+    }
+    parsed
+});
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Site {
+    /// Absolute, ending with `BASE_PATH`, e.g. `https://example.org/portfolio/`.
+    pub url: String,
+    pub title: String,
+    pub description: String,
+}
+
+impl Site {
+    /// The absolute URL of a route path such as `/writing`.
+    pub fn absolute(&self, path: &str) -> String {
+        format!("{}{path}", self.url.trim_end_matches('/'))
+    }
+}
+
+fn parse(source: &str) -> Result<Site, String> {
+    let site: Site = toml::from_str(source).map_err(|err| err.to_string())?;
+    let host_and_path = site
+        .url
+        .strip_prefix("https://")
+        .or_else(|| site.url.strip_prefix("http://"))
+        .ok_or_else(|| format!("url `{}` is not an http(s) URL", site.url))?;
+    if !host_and_path.ends_with(BASE_PATH) || host_and_path.len() == BASE_PATH.len() {
+        return Err(format!("url `{}` must be a host followed by the base path {BASE_PATH}", site.url));
+    }
+    if site.title.trim().is_empty() {
+        return Err("title is empty".to_string());
+    }
+    Ok(site)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_site_parses() {
+        let site = SITE.as_ref().unwrap_or_else(|err| panic!("{err}"));
+        assert!(site.absolute("/writing").ends_with(&format!("{BASE_PATH}writing")));
+    }
+
+    #[test]
+    fn url_must_end_with_the_base_path() {
+        let err = parse("url = \"https://example.org/\"\ntitle = \"T\"\ndescription = \"D\"\n").unwrap_err();
+        assert!(err.contains("base path"), "{err}");
+        assert!(parse("url = \"ftp://example.org/x\"\ntitle = \"T\"\ndescription = \"D\"\n").is_err());
+    }
+}
@@ -0,0 +1,142 @@
+//! Write tracking for multi-element scans of `CONTIGUOUS_ARRAY`.
+//!
+//! `inspect_element` makes each element access atomic, but a scan over many
+//! indices is not: a worker can write index 3 after the scan has read it and
+//! before the scan reaches index 7. The demo helpers bump a per-index version
+//! on every write and a structure epoch on every push and pop. A scan records
+//! both before it starts and validates them when it ends, seqlock style, so
+//! a torn read is reported instead of silently averaged in.
+
+use std::fmt;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+static VERSIONS: LazyLock<Mutex<Vec<u64>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+static STRUCTURE_EPOCH: AtomicU64 = AtomicU64::new(0);
+
+pub fn record_write(idx: usize) {
+    let mut versions = VERSIONS.lock().unwrap();
+    if versions.len() <= idx {
+        versions.resize(idx + 1, 0);
+    }
+    versions[idx] += 1;
+}
+
+pub fn record_resize() {
+    STRUCTURE_EPOCH.fetch_add(1, Ordering::AcqRel);
+}
+
+fn versions(range: Range<usize>) -> Vec<u64> {
+    let versions = VERSIONS.lock().unwrap();
+    range.map(|idx| versions.get(idx).copied().unwrap_or(0)).collect()
+}
+
+/// Versions of a range captured when a scan starts.
+pub struct ScanGuard {
+    range: Range<usize>,
+    epoch: u64,
+    versions: Vec<u64>,
+}
+
+impl ScanGuard {
+    pub fn begin(range: Range<usize>) -> Self {
+        Self {
+            epoch: STRUCTURE_EPOCH.load(Ordering::Acquire),
+            versions: versions(range.clone()),
+            range,
+        }
+    }
+
+    pub fn finish(self) -> ScanVerdict {
+        let resized = STRUCTURE_EPOCH.load(Ordering::Acquire) != self.epoch;
+        let written: Vec<usize> = versions(self.range.clone())
+            .into_iter()
+            .zip(self.versions)
+            .zip(self.range)
+            .filter(|((now, before), _)| now != before)
+            .map(|(_, idx)| idx)
+            .collect();
+        if written.is_empty() && !resized {
+            ScanVerdict::Consistent
+        } else {
+            ScanVerdict::Torn { written, resized }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScanVerdict {
+    /// No write or resize touched the range while it was being read.
+    Consistent,
+    /// The values mix states from before and after these writes.
+    Torn { written: Vec<usize>, resized: bool },
+}
+
+impl ScanVerdict {
+    pub fn is_consistent(&self) -> bool {
+        *self == ScanVerdict::Consistent
+    }
+}
+
+impl fmt::Display for ScanVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanVerdict::Consistent => write!(f, "consistent"),
+            ScanVerdict::Torn { written, resized } => {
+                write!(f, "TORN")?;
+                if !written.is_empty() {
+                    write!(f, ": {} index(es) written mid-scan {:?}", written.len(), written)?;
+                }
+                if *resized {
+                    write!(f, " (push/pop during scan)")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Sum, count, min and max of one section; sections merge into the global.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SectionStats {
+    pub sum: i64,
+    pub count: usize,
+    pub min: i32,
+    pub max: i32,
+}
+
+impl Default for SectionStats {
+    fn default() -> Self {
+        Self {
+            sum: 0,
+            count: 0,
+            min: i32::MAX,
+            max: i32::MIN,
+        }
+    }
+}
+
+impl SectionStats {
+    pub fn add(&mut self, val: i32) {
+        self.sum += val as i64;
+        self.count += 1;
+        self.min = self.min.min(val);
+        self.max = self.max.max(val);
+    }
+
+    pub fn merge(&mut self, other: &SectionStats) {
+        self.sum += other.sum;
+        self.count += other.count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    pub fn avg(&self) -> i64 {
+        if self.count > 0 {
+            self.sum / self.count as i64
+        } else {
+            0
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! Tracing setup for the demos.
+//!
+//! Every event is forwarded to the platform sink (the browser console on
+//! wasm, a `fmt` writer on native) and mirrored into a small ring buffer that
+//! the in-page `TraceViewer` polls. The level for this crate lives behind a
+//! reload handle so the viewer can switch debug detail on and off at runtime.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, OnceLock};
+
+use dioxus::prelude::*;
+use tracing::field::{Field, Visit};
+use tracing::{span, Event, Level, Subscriber};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, Layer, Registry};
+
+use crate::ui::{Button, LogView, Panel, SpecCard, Variant};
+
+const CRATE_TARGET: &str = "smg_portfolio";
+const TRACE_CAPACITY: usize = 400;
+
+static TRACE_BUFFER: LazyLock<Mutex<VecDeque<TraceRecord>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::with_capacity(TRACE_CAPACITY)));
+static TRACE_SEQ: AtomicU64 = AtomicU64::new(0);
+static FILTER_HANDLE: OnceLock<reload::Handle<Targets, Registry>> = OnceLock::new();
+
+/// One event as captured for the in-page viewer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceRecord {
+    pub seq: u64,
+    pub level: Level,
+    /// Enclosing spans from the root down, e.g. `matrix_worker{worker_id=2}`.
+    pub spans: String,
+    pub message: String,
+}
+
+/// Installs the global subscriber. Must run before `dioxus::launch`, which
+/// otherwise installs its own default logger.
+pub fn init() {
+    let (filter, handle) = reload::Layer::new(targets(LevelFilter::INFO));
+    let registry = Registry::default().with(filter).with(PageLayer);
+
+    #[cfg(target_arch = "wasm32")]
+    let installed = registry
+        .with(tracing_wasm::WASMLayer::new(
+            tracing_wasm::WASMLayerConfigBuilder::new()
+                .set_max_level(Level::TRACE)
+                .build(),
+        ))
+        .try_init();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let installed = registry.with(tracing_subscriber::fmt::layer()).try_init();
+
+    if installed.is_ok() {
+        let _ = FILTER_HANDLE.set(handle);
+    }
+}
+
+/// Level applied to this crate's spans and events. Dependencies stay at WARN
+/// so the viewer is not flooded by renderer internals.
+pub fn level() -> LevelFilter {
+    FILTER_HANDLE
+        .get()
+        .and_then(|handle| {
+            handle
+                .with_current(|targets| {
+                    targets
+                        .iter()
+                        .find(|(target, _)| *target == CRATE_TARGET)
+                        .map(|(_, level)| level)
+                })
+                .ok()
+                .flatten()
+        })
+        .unwrap_or(LevelFilter::INFO)
+}
+
+pub fn set_level(level: LevelFilter) {
+    if let Some(handle) = FILTER_HANDLE.get() {
+        let _ = handle.reload(targets(level));
+        tracing::info!(%level, "trace level changed");
+    }
+}
+
+/// Sequence number of the newest captured event; cheap to poll.
+pub fn latest_seq() -> u64 {
+    TRACE_SEQ.load(Ordering::Acquire)
+}
+
+pub fn records() -> Vec<TraceRecord> {
+    TRACE_BUFFER.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn clear_records() {
+    TRACE_BUFFER.lock().unwrap().clear();
+    TRACE_SEQ.fetch_add(1, Ordering::AcqRel);
+}
+
+fn targets(level: LevelFilter) -> Targets {
+    Targets::new()
+        .with_default(LevelFilter::WARN)
+        .with_target(CRATE_TARGET, level)
+}
+
+/// Span fields formatted once at creation, stored in the span extensions.
+struct SpanFields(String);
+
+/// Layer that copies events into `TRACE_BUFFER`.
+struct PageLayer;
+
+impl<S> Layer<S> for PageLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanFields(visitor.fields));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut spans = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if !spans.is_empty() {
+                    spans.push(':');
+                }
+                spans.push_str(span.name());
+                if let Some(SpanFields(fields)) = span.extensions().get::<SpanFields>() {
+                    if !fields.is_empty() {
+                        let _ = write!(spans, "{{{fields}}}");
+                    }
+                }
+            }
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let message = if visitor.fields.is_empty() {
+            visitor.message
+        } else {
+            format!("{} {}", visitor.message, visitor.fields)
+        };
+
+        let seq = TRACE_SEQ.fetch_add(1, Ordering::AcqRel) + 1;
+        let mut buffer = TRACE_BUFFER.lock().unwrap();
+        if buffer.len() == TRACE_CAPACITY {
+            buffer.pop_front();
+        }
+        buffer.push_back(TraceRecord {
+            seq,
+            level: *event.metadata().level(),
+            spans,
+            message,
+        });
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+            return;
+        }
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        let _ = write!(self.fields, "{}={value:?}", field.name());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_debug(field, &format_args!("{value}"));
+    }
+}
+
+/// In-page view of the events captured by `telemetry`.
+#[component]
+pub fn TraceViewer() -> Element {
+    let mut records = use_signal(records);
+    let mut level = use_signal(level);
+    let mut min_level = use_signal(|| tracing::Level::TRACE);
+    let mut filter = use_signal(String::new);
+
+    // The subscriber lives outside the Dioxus runtime, so poll its buffer.
+    use_future(move || async move {
+        let mut seen = latest_seq();
+        loop {
+            gloo_timers::future::TimeoutFuture::new(250).await;
+            let latest = latest_seq();
+            if latest != seen {
+                seen = latest;
+                records.set(records());
+            }
+        }
+    });
+
+    let needle = filter.read().to_lowercase();
+    let visible: Vec<TraceRecord> = records
+        .read()
+        .iter()
+        .rev()
+        .filter(|record| record.level <= *min_level.read())
+        .filter(|record| {
+            needle.is_empty()
+                || record.message.to_lowercase().contains(&needle)
+                || record.spans.to_lowercase().contains(&needle)
+        })
+        .cloned()
+        .collect();
+
+    rsx! {
+        Panel { id: "trace-area",
+            SpecCard { label: "TRACING:", value: "tracing + in-page subscriber",
+                "Every worker spawn, "
+                strong { "inspect_element" }
+                " call, push/pop result and snapshot publication is recorded as a tracing event. "
+                "Raise the capture level to DEBUG or TRACE to see per-element detail without a rebuild."
+            }
+
+            div { class: "trace-toolbar",
+                label {
+                    "Capture "
+                    select {
+                        value: "{level}",
+                        onchange: move |evt| {
+                            let new_level = match evt.value().as_str() {
+                                "trace" => LevelFilter::TRACE,
+                                "debug" => LevelFilter::DEBUG,
+                                _ => LevelFilter::INFO,
+                            };
+                            set_level(new_level);
+                            level.set(new_level);
+                        },
+                        option { value: "info", selected: *level.read() == LevelFilter::INFO, "INFO" }
+                        option { value: "debug", selected: *level.read() == LevelFilter::DEBUG, "DEBUG" }
+                        option { value: "trace", selected: *level.read() == LevelFilter::TRACE, "TRACE" }
+                    }
+                }
+                label {
+                    "Show "
+                    select {
+                        onchange: move |evt| {
+                            min_level.set(evt.value().parse().unwrap_or(tracing::Level::TRACE));
+                        },
+                        option { value: "trace", "ALL" }
+                        option { value: "debug", "DEBUG+" }
+                        option { value: "info", "INFO+" }
+                        option { value: "warn", "WARN+" }
+                    }
+                }
+                input {
+                    class: "trace-filter",
+                    placeholder: "filter by message or span...",
+                    value: "{filter}",
+                    oninput: move |evt| filter.set(evt.value()),
+                }
+                Button {
+                    variant: Variant::Secondary,
+                    onclick: move |_| {
+                        clear_records();
+                        records.set(vec![]);
+                    },
+                    "CLEAR"
+                }
+            }
+
+            LogView {
+                title: "Trace Events ({visible.len()})",
+                class: "trace-log",
+                empty: visible.is_empty(),
+                placeholder: "No trace events match the current filter.",
+                for record in visible.iter() {
+                    div {
+                        class: "log-entry trace-entry trace-{record.level.as_str().to_lowercase()}",
+                        key: "{record.seq}",
+                        span { class: "trace-level", "{record.level}" }
+                        if !record.spans.is_empty() {
+                            span { class: "trace-spans", "{record.spans}" }
+                        }
+                        span { "{record.message}" }
+                    }
+                }
+            }
+        }
+    }
+}
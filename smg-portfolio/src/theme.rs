@@ -0,0 +1,342 @@
+//! Light, dark and high-contrast palettes.
+//!
+//! Colours live here, not in the stylesheets: `stylesheet` turns every
+//! `Palette` into CSS custom properties, and `main.css` and `resume.css`
+//! only refer to those. Without a saved choice the system decides through
+//! `prefers-color-scheme` and `prefers-contrast`; picking a theme sets
+//! `data-theme` on `<html>` and saves it in a `persist::Slot`.
+//! `boot_script` applies a saved theme before the first paint, so a
+//! pre-rendered page does not flash the light palette.
+
+use std::sync::LazyLock;
+
+use dioxus::prelude::*;
+
+use crate::{browser, persist};
+
+const SLOT: persist::Slot = persist::Slot {
+    key: "smg-portfolio.theme",
+    file: "theme.txt",
+};
+const ATTRIBUTE: &str = "data-theme";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Theme {
+    Light,
+    Dark,
+    HighContrast,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::Light, Theme::Dark, Theme::HighContrast];
+
+    /// The `data-theme` value.
+    pub fn id(self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::HighContrast => "high-contrast",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::Light => "LIGHT",
+            Theme::Dark => "DARK",
+            Theme::HighContrast => "HIGH CONTRAST",
+        }
+    }
+
+    pub fn palette(self) -> &'static Palette {
+        match self {
+            Theme::Light => &LIGHT,
+            Theme::Dark => &DARK,
+            Theme::HighContrast => &HIGH_CONTRAST,
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Theme> {
+        Theme::ALL.into_iter().find(|theme| theme.id() == id)
+    }
+}
+
+/// Theme tokens, one CSS custom property each.
+pub struct Palette {
+    /// Page background.
+    pub bg: &'static str,
+    /// Cards, inputs and panels on the page.
+    pub surface: &'static str,
+    /// Cards that sit on a surface, such as timeline entries.
+    pub raised: &'static str,
+    pub border: &'static str,
+    pub shadow: &'static str,
+    pub text: &'static str,
+    pub text_muted: &'static str,
+    pub accent: &'static str,
+    pub accent_hover: &'static str,
+    /// Text on `accent`, `danger` and other saturated fills.
+    pub on_accent: &'static str,
+    /// Text on `success`, `warning` and `info` fills.
+    pub on_status: &'static str,
+    pub glow: &'static str,
+    pub success: &'static str,
+    pub success_hover: &'static str,
+    /// `success` dark enough for text on `bg`.
+    pub success_text: &'static str,
+    pub warning: &'static str,
+    pub warning_hover: &'static str,
+    pub warning_text: &'static str,
+    pub danger: &'static str,
+    pub danger_hover: &'static str,
+    pub danger_deep: &'static str,
+    pub info: &'static str,
+    pub info_hover: &'static str,
+    pub highlight: &'static str,
+    /// Code blocks, the operation log, the console and the trace viewer.
+    pub code_bg: &'static str,
+    pub code_text: &'static str,
+    pub code_border: &'static str,
+    pub code_rule: &'static str,
+    pub code_muted: &'static str,
+    pub code_keyword: &'static str,
+    pub code_type: &'static str,
+}
+
+impl Palette {
+    fn tokens(&self) -> [(&'static str, &'static str); 31] {
+        [
+            ("bg", self.bg),
+            ("card-bg", self.surface),
+            ("raised", self.raised),
+            ("border", self.border),
+            ("shadow", self.shadow),
+            ("text", self.text),
+            ("text-muted", self.text_muted),
+            ("accent", self.accent),
+            ("accent-hover", self.accent_hover),
+            ("on-accent", self.on_accent),
+            ("on-status", self.on_status),
+            ("glow", self.glow),
+            ("success", self.success),
+            ("success-hover", self.success_hover),
+            ("success-text", self.success_text),
+            ("warning", self.warning),
+            ("warning-hover", self.warning_hover),
+            ("warning-text", self.warning_text),
+            ("danger", self.danger),
+            ("danger-hover", self.danger_hover),
+            ("danger-deep", self.danger_deep),
+            ("info", self.info),
+            ("info-hover", self.info_hover),
+            ("highlight", self.highlight),
+            ("code-bg", self.code_bg),
+            ("code-text", self.code_text),
+            ("code-border", self.code_border),
+            ("code-rule", self.code_rule),
+            ("code-muted", self.code_muted),
+            ("code-keyword", self.code_keyword),
+            ("code-type", self.code_type),
+        ]
+    }
+
+    fn declarations(&self) -> String {
+        self.tokens().iter().map(|(name, value)| format!("--{name}: {value}; ")).collect()
+    }
+}
+
+/// Nord, as the site has always looked.
+const LIGHT: Palette = Palette {
+    bg: "#ECEFF4",
+    surface: "#E5E9F0",
+    raised: "#FFFFFF",
+    border: "#D8DEE9",
+    shadow: "rgba(46, 52, 64, 0.1)",
+    text: "#2E3440",
+    text_muted: "#4C566A",
+    accent: "#5E81AC",
+    accent_hover: "#81A1C1",
+    on_accent: "#FFFFFF",
+    on_status: "#2E3440",
+    glow: "#EEF6FF",
+    success: "#A3BE8C",
+    success_hover: "#8FBC7B",
+    success_text: "#5E8C4A",
+    warning: "#EBCB8B",
+    warning_hover: "#D8B66F",
+    warning_text: "#B08A2E",
+    danger: "#BF616A",
+    danger_hover: "#A54E56",
+    danger_deep: "#943441",
+    info: "#88C0D0",
+    info_hover: "#7AB4C4",
+    highlight: "#B48EAD",
+    code_bg: "#2E3440",
+    code_text: "#D8DEE9",
+    code_border: "#3B4252",
+    code_rule: "#4C566A",
+    code_muted: "#7B88A1",
+    code_keyword: "#81A1C1",
+    code_type: "#8FBCBB",
+};
+
+/// Nord's polar night for the page, with the same frost and aurora colours.
+const DARK: Palette = Palette {
+    bg: "#2E3440",
+    surface: "#3B4252",
+    raised: "#434C5E",
+    border: "#4C566A",
+    shadow: "rgba(0, 0, 0, 0.35)",
+    text: "#ECEFF4",
+    text_muted: "#C7CEDB",
+    accent: "#81A1C1",
+    accent_hover: "#88C0D0",
+    on_accent: "#2E3440",
+    on_status: "#2E3440",
+    glow: "#5E81AC",
+    success: "#A3BE8C",
+    success_hover: "#8FBC7B",
+    success_text: "#A3BE8C",
+    warning: "#EBCB8B",
+    warning_hover: "#D8B66F",
+    warning_text: "#EBCB8B",
+    danger: "#BF616A",
+    danger_hover: "#A54E56",
+    danger_deep: "#943441",
+    info: "#88C0D0",
+    info_hover: "#7AB4C4",
+    highlight: "#B48EAD",
+    code_bg: "#242933",
+    code_text: "#D8DEE9",
+    code_border: "#4C566A",
+    code_rule: "#4C566A",
+    code_muted: "#8C97AD",
+    code_keyword: "#81A1C1",
+    code_type: "#8FBCBB",
+};
+
+/// Black on white, with every colour dark or saturated enough for AAA text
+/// contrast. Code panels turn light too, so one set of status colours works
+/// everywhere.
+const HIGH_CONTRAST: Palette = Palette {
+    bg: "#FFFFFF",
+    surface: "#FFFFFF",
+    raised: "#FFFFFF",
+    border: "#000000",
+    shadow: "rgba(0, 0, 0, 0.25)",
+    text: "#000000",
+    text_muted: "#1F1F1F",
+    accent: "#0037A0",
+    accent_hover: "#002470",
+    on_accent: "#FFFFFF",
+    on_status: "#000000",
+    glow: "#FFD400",
+    success: "#58D058",
+    success_hover: "#3CB83C",
+    success_text: "#005A00",
+    warning: "#FFD400",
+    warning_hover: "#E6BE00",
+    warning_text: "#5C4300",
+    danger: "#B00020",
+    danger_hover: "#8A0019",
+    danger_deep: "#600011",
+    info: "#5CC8E6",
+    info_hover: "#38B4D6",
+    highlight: "#6A1B9A",
+    code_bg: "#FFFFFF",
+    code_text: "#000000",
+    code_border: "#000000",
+    code_rule: "#000000",
+    code_muted: "#333333",
+    code_keyword: "#0037A0",
+    code_type: "#005A5A",
+};
+
+/// The custom properties for every theme, light by default.
+pub static STYLESHEET: LazyLock<String> = LazyLock::new(|| {
+    let mut css = format!(":root {{ {}}}\n", LIGHT.declarations());
+    css += &format!(
+        "@media (prefers-color-scheme: dark) {{ :root:not([{ATTRIBUTE}]) {{ {}}} }}\n",
+        DARK.declarations()
+    );
+    css += &format!(
+        "@media (prefers-contrast: more) {{ :root:not([{ATTRIBUTE}]) {{ {}}} }}\n",
+        HIGH_CONTRAST.declarations()
+    );
+    for theme in Theme::ALL {
+        css += &format!(":root[{ATTRIBUTE}=\"{}\"] {{ {}}}\n", theme.id(), theme.palette().declarations());
+    }
+    css
+});
+
+/// Inline script for `<head>` that sets `data-theme` from the saved choice
+/// before anything is drawn.
+pub fn boot_script() -> String {
+    format!(
+        "try {{ var theme = localStorage.getItem({key:?}); \
+         if (theme) document.documentElement.setAttribute({ATTRIBUTE:?}, theme); }} catch (err) {{}}",
+        key = SLOT.key
+    )
+}
+
+/// The saved choice; `None` follows the system.
+pub fn load() -> Option<Theme> {
+    match persist::read(&SLOT) {
+        Ok(saved) => saved.as_deref().and_then(Theme::from_id),
+        Err(err) => {
+            tracing::warn!(%err, "saved theme unreadable");
+            None
+        }
+    }
+}
+
+pub fn save(choice: Option<Theme>) -> Result<(), String> {
+    match choice {
+        Some(theme) => persist::write(&SLOT, theme.id()),
+        None => persist::remove(&SLOT),
+    }
+}
+
+pub fn apply(choice: Option<Theme>) {
+    browser::set_root_attribute(ATTRIBUTE, choice.map(Theme::id));
+}
+
+/// AUTO, LIGHT, DARK, HIGH CONTRAST, then AUTO again.
+pub fn next(choice: Option<Theme>) -> Option<Theme> {
+    match choice {
+        None => Some(Theme::Light),
+        Some(Theme::Light) => Some(Theme::Dark),
+        Some(Theme::Dark) => Some(Theme::HighContrast),
+        Some(Theme::HighContrast) => None,
+    }
+}
+
+/// Cycles AUTO, LIGHT, DARK and HIGH CONTRAST. AUTO follows the system and
+/// forgets the saved choice.
+#[component]
+pub fn ThemeToggle() -> Element {
+    let mut choice = use_signal(|| None::<Theme>);
+    // Pre-rendered pages say AUTO; the saved choice is only known in the
+    // browser, where `boot_script` has already applied it.
+    use_effect(move || {
+        let saved = load();
+        apply(saved);
+        choice.set(saved);
+    });
+    let label = choice().map_or("AUTO", Theme::label);
+
+    rsx! {
+        button {
+            class: "theme-toggle",
+            title: "Colour theme",
+            onclick: move |_| {
+                let next = next(choice());
+                apply(next);
+                if let Err(err) = save(next) {
+                    warn!(%err, "theme not saved");
+                }
+                choice.set(next);
+            },
+            "THEME: {label}"
+        }
+    }
+}
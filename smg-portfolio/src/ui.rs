@@ -0,0 +1,169 @@
+//! The building blocks every demo section is made of.
+//!
+//! A section is a `Panel` holding a `SpecCard`, a row of `Controls` with
+//! `Button`s, whatever visualizer the primitive needs, and usually a
+//! `LogView`. The components only pick classes from `main.css`, so colours
+//! still come from the theme tokens.
+
+use dioxus::prelude::*;
+
+/// How a `Button` is filled, i.e. what pressing it does to the demo.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Variant {
+    /// A single operation, such as PUSH.
+    #[default]
+    Primary,
+    /// Stops, removes or reads without changing anything.
+    Secondary,
+    /// Starts concurrent workers.
+    Burst,
+    /// Starts workers that race each other on purpose.
+    Race,
+    /// Empties the primitive.
+    Reset,
+    /// Fills the primitive or loads something.
+    Seed,
+}
+
+impl Variant {
+    fn class(self) -> &'static str {
+        match self {
+            Variant::Primary => "btn-inc",
+            Variant::Secondary => "btn-dec",
+            Variant::Burst => "btn-burst",
+            Variant::Race => "btn-race",
+            Variant::Reset => "btn-reset",
+            Variant::Seed => "btn-seed",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Size {
+    /// Fills its share of a `Controls` row.
+    #[default]
+    Regular,
+    /// Sized to its label, for buttons inside lists and toolbars.
+    Compact,
+}
+
+/// A `.btn`. `loading` disables it and marks it busy while the work it
+/// started is still running.
+#[component]
+pub fn Button(
+    #[props(default)] variant: Variant,
+    #[props(default)] size: Size,
+    #[props(default)] disabled: bool,
+    #[props(default)] loading: bool,
+    /// Extra classes, e.g. `stage-remove`.
+    #[props(default)]
+    class: String,
+    #[props(default)] title: String,
+    onclick: EventHandler<MouseEvent>,
+    children: Element,
+) -> Element {
+    let mut classes = format!("btn {}", variant.class());
+    if size == Size::Compact {
+        classes += " btn-compact";
+    }
+    if loading {
+        classes += " btn-loading";
+    }
+    if !class.is_empty() {
+        classes += &format!(" {class}");
+    }
+
+    rsx! {
+        button {
+            class: classes,
+            disabled: disabled || loading,
+            title: (!title.is_empty()).then_some(title),
+            aria_busy: loading,
+            onclick: move |event| onclick.call(event),
+            {children}
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChipVariant {
+    /// Inline next to a spec value.
+    #[default]
+    Small,
+    /// One of a row of large links, as under the hero.
+    Large,
+}
+
+/// A link that opens outside the site in a new tab.
+#[component]
+pub fn Chip(#[props(default)] variant: ChipVariant, href: String, children: Element) -> Element {
+    let class = match variant {
+        ChipVariant::Small => "small-link-chip",
+        ChipVariant::Large => "link-chip",
+    };
+
+    rsx! {
+        a { class, href, target: "_blank", rel: "noopener", {children} }
+    }
+}
+
+/// One demo section. `id` is what the page shortcuts and `DemoSlot` point
+/// at, and what gives the section its card style.
+#[component]
+pub fn Panel(id: &'static str, #[props(default)] class: String, children: Element) -> Element {
+    rsx! {
+        div { id, class, {children} }
+    }
+}
+
+/// The `LABEL: value` line that opens a section, with `chips` after the
+/// value, `children` as the description and `notes` below it.
+#[component]
+pub fn SpecCard(
+    label: String,
+    value: String,
+    #[props(default = VNode::empty())] chips: Element,
+    #[props(default = VNode::empty())] notes: Element,
+    children: Element,
+) -> Element {
+    rsx! {
+        div { class: "engine-spec",
+            div { class: "spec-line",
+                span { class: "spec-label", "{label}" }
+                span { class: "spec-value", "{value}" }
+                {chips}
+            }
+            p { class: "spec-description", {children} }
+            {notes}
+        }
+    }
+}
+
+/// The row of `Button`s under a `SpecCard`.
+#[component]
+pub fn Controls(children: Element) -> Element {
+    rsx! {
+        div { class: "controls", {children} }
+    }
+}
+
+/// A dark, scrolling log. `children` are the `.log-entry` rows; while
+/// `empty`, `placeholder` stands in for them.
+#[component]
+pub fn LogView(
+    title: String,
+    empty: bool,
+    placeholder: String,
+    #[props(default)] class: String,
+    children: Element,
+) -> Element {
+    rsx! {
+        div { class: "race-log {class}",
+            div { class: "log-header", "{title}" }
+            {children}
+            if empty {
+                div { class: "log-entry log-empty", "{placeholder}" }
+            }
+        }
+    }
+}